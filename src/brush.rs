@@ -0,0 +1,400 @@
+use canvas::Canvas;
+use enums::ExtendMode;
+use math::{ColorF, Point2F};
+
+/// What a backend needs to know to paint with a brush at the moment of a draw call.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Paint {
+    Solid {
+        color: ColorF,
+        opacity: f32,
+    },
+    LinearGradient {
+        start: Point2F,
+        end: Point2F,
+        stops: Vec<GradientStop>,
+        extend_mode: ExtendMode,
+        opacity: f32,
+    },
+    RadialGradient {
+        center: Point2F,
+        gradient_origin_offset: Point2F,
+        radius_x: f32,
+        radius_y: f32,
+        stops: Vec<GradientStop>,
+        extend_mode: ExtendMode,
+        opacity: f32,
+    },
+}
+
+pub trait Brush {
+    fn get_opacity(&self) -> f32;
+    fn set_opacity(&mut self, opacity: f32);
+    fn paint(&self) -> Paint;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolidColorBrush {
+    color: ColorF,
+    opacity: f32,
+}
+
+impl SolidColorBrush {
+    pub fn new(color: ColorF, opacity: f32) -> SolidColorBrush {
+        SolidColorBrush { color, opacity }
+    }
+
+    pub fn create(canvas: &dyn Canvas) -> SolidColorBrushBuilder<'_> {
+        SolidColorBrushBuilder::new(canvas)
+    }
+
+    pub fn set_color(&mut self, color: &ColorF) {
+        self.color = *color;
+    }
+
+    pub fn get_color(&self) -> ColorF {
+        self.color
+    }
+}
+
+impl Brush for SolidColorBrush {
+    fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    fn paint(&self) -> Paint {
+        Paint::Solid { color: self.color, opacity: self.opacity }
+    }
+}
+
+pub struct SolidColorBrushBuilder<'a> {
+    canvas: &'a dyn Canvas,
+    color: Option<ColorF>,
+    opacity: f32,
+}
+
+impl<'a> SolidColorBrushBuilder<'a> {
+    pub fn new(canvas: &'a dyn Canvas) -> Self {
+        SolidColorBrushBuilder {
+            canvas,
+            color: None,
+            opacity: 1.0,
+        }
+    }
+
+    pub fn with_color(mut self, color: impl Into<ColorF>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    pub fn build(self) -> SolidColorBrush {
+        let color = self.color.expect("`color` must be specified");
+        self.canvas.create_solid_color_brush(color, self.opacity)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GradientStop {
+    pub position: f32,
+    pub color: ColorF,
+}
+
+impl From<(f32, ColorF)> for GradientStop {
+    fn from((position, color): (f32, ColorF)) -> GradientStop {
+        GradientStop { position, color }
+    }
+}
+
+/// Paints an area with a linear gradient.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearGradientBrush {
+    start: Point2F,
+    end: Point2F,
+    stops: Vec<GradientStop>,
+    extend_mode: ExtendMode,
+    opacity: f32,
+}
+
+impl LinearGradientBrush {
+    pub fn create(canvas: &dyn Canvas) -> LinearGradientBrushBuilder<'_> {
+        LinearGradientBrushBuilder::new(canvas)
+    }
+
+    pub fn get_start_point(&self) -> Point2F {
+        self.start
+    }
+
+    pub fn get_end_point(&self) -> Point2F {
+        self.end
+    }
+
+    pub fn set_start_point(&mut self, point: Point2F) {
+        self.start = point;
+    }
+
+    pub fn set_end_point(&mut self, point: Point2F) {
+        self.end = point;
+    }
+
+    pub fn get_stops(&self) -> &[GradientStop] {
+        &self.stops
+    }
+
+    pub fn get_extend_mode(&self) -> ExtendMode {
+        self.extend_mode
+    }
+}
+
+impl Brush for LinearGradientBrush {
+    fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    fn paint(&self) -> Paint {
+        Paint::LinearGradient {
+            start: self.start,
+            end: self.end,
+            stops: self.stops.clone(),
+            extend_mode: self.extend_mode,
+            opacity: self.opacity,
+        }
+    }
+}
+
+/// Starts with both points at the origin, clamped extension and full opacity, like the
+/// Direct2D builder. The canvas only ties the brush to a device, as it does there.
+pub struct LinearGradientBrushBuilder<'a> {
+    canvas: &'a dyn Canvas,
+    brush: LinearGradientBrush,
+}
+
+impl<'a> LinearGradientBrushBuilder<'a> {
+    pub fn new(canvas: &'a dyn Canvas) -> Self {
+        LinearGradientBrushBuilder {
+            canvas,
+            brush: LinearGradientBrush {
+                start: Point2F::default(),
+                end: Point2F::default(),
+                stops: Vec::new(),
+                extend_mode: ExtendMode::Clamp,
+                opacity: 1.0,
+            },
+        }
+    }
+
+    pub fn with_start(mut self, start: Point2F) -> Self {
+        self.brush.start = start;
+        self
+    }
+
+    pub fn with_end(mut self, end: Point2F) -> Self {
+        self.brush.end = end;
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.brush.opacity = opacity;
+        self
+    }
+
+    pub fn with_extend_mode(mut self, mode: ExtendMode) -> Self {
+        self.brush.extend_mode = mode;
+        self
+    }
+
+    pub fn with_stop(mut self, stop: impl Into<GradientStop>) -> Self {
+        self.brush.stops.push(stop.into());
+        self
+    }
+
+    pub fn with_stops(mut self, stops: &[GradientStop]) -> Self {
+        self.brush.stops.extend_from_slice(stops);
+        self
+    }
+
+    pub fn build(self) -> LinearGradientBrush {
+        assert!(!self.brush.stops.is_empty(), "at least one gradient stop must be specified");
+        self.brush
+    }
+}
+
+/// Paints an area with a radial gradient.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadialGradientBrush {
+    center: Point2F,
+    gradient_origin_offset: Point2F,
+    radius_x: f32,
+    radius_y: f32,
+    stops: Vec<GradientStop>,
+    extend_mode: ExtendMode,
+    opacity: f32,
+}
+
+impl RadialGradientBrush {
+    pub fn create(canvas: &dyn Canvas) -> RadialGradientBrushBuilder<'_> {
+        RadialGradientBrushBuilder::new(canvas)
+    }
+
+    pub fn get_center(&self) -> Point2F {
+        self.center
+    }
+
+    pub fn get_gradient_origin_offset(&self) -> Point2F {
+        self.gradient_origin_offset
+    }
+
+    pub fn get_radius_x(&self) -> f32 {
+        self.radius_x
+    }
+
+    pub fn get_radius_y(&self) -> f32 {
+        self.radius_y
+    }
+
+    pub fn set_center(&mut self, center: Point2F) {
+        self.center = center;
+    }
+
+    pub fn set_gradient_origin_offset(&mut self, offset: Point2F) {
+        self.gradient_origin_offset = offset;
+    }
+
+    pub fn set_radius_x(&mut self, radius: f32) {
+        self.radius_x = radius;
+    }
+
+    pub fn set_radius_y(&mut self, radius: f32) {
+        self.radius_y = radius;
+    }
+
+    pub fn get_stops(&self) -> &[GradientStop] {
+        &self.stops
+    }
+
+    pub fn get_extend_mode(&self) -> ExtendMode {
+        self.extend_mode
+    }
+}
+
+impl Brush for RadialGradientBrush {
+    fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    fn paint(&self) -> Paint {
+        Paint::RadialGradient {
+            center: self.center,
+            gradient_origin_offset: self.gradient_origin_offset,
+            radius_x: self.radius_x,
+            radius_y: self.radius_y,
+            stops: self.stops.clone(),
+            extend_mode: self.extend_mode,
+            opacity: self.opacity,
+        }
+    }
+}
+
+/// Starts with the center at the origin, zero radii, clamped extension and full
+/// opacity, like the Direct2D builder.
+pub struct RadialGradientBrushBuilder<'a> {
+    canvas: &'a dyn Canvas,
+    brush: RadialGradientBrush,
+}
+
+impl<'a> RadialGradientBrushBuilder<'a> {
+    pub fn new(canvas: &'a dyn Canvas) -> Self {
+        RadialGradientBrushBuilder {
+            canvas,
+            brush: RadialGradientBrush {
+                center: Point2F::default(),
+                gradient_origin_offset: Point2F::default(),
+                radius_x: 0.0,
+                radius_y: 0.0,
+                stops: Vec::new(),
+                extend_mode: ExtendMode::Clamp,
+                opacity: 1.0,
+            },
+        }
+    }
+
+    pub fn with_center(mut self, center: Point2F) -> Self {
+        self.brush.center = center;
+        self
+    }
+
+    pub fn with_origin_offset(mut self, origin_offset: Point2F) -> Self {
+        self.brush.gradient_origin_offset = origin_offset;
+        self
+    }
+
+    pub fn with_radius(mut self, radius_x: f32, radius_y: f32) -> Self {
+        self.brush.radius_x = radius_x;
+        self.brush.radius_y = radius_y;
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.brush.opacity = opacity;
+        self
+    }
+
+    pub fn with_extend_mode(mut self, mode: ExtendMode) -> Self {
+        self.brush.extend_mode = mode;
+        self
+    }
+
+    pub fn with_stop(mut self, stop: impl Into<GradientStop>) -> Self {
+        self.brush.stops.push(stop.into());
+        self
+    }
+
+    pub fn with_stops(mut self, stops: &[GradientStop]) -> Self {
+        self.brush.stops.extend_from_slice(stops);
+        self
+    }
+
+    pub fn build(self) -> RadialGradientBrush {
+        assert!(!self.brush.stops.is_empty(), "at least one gradient stop must be specified");
+        self.brush
+    }
+}
+
+/// A paint is also a brush, which is how recorded draw calls are replayed.
+impl Brush for Paint {
+    fn get_opacity(&self) -> f32 {
+        match *self {
+            Paint::Solid { opacity, .. }
+            | Paint::LinearGradient { opacity, .. }
+            | Paint::RadialGradient { opacity, .. } => opacity,
+        }
+    }
+
+    fn set_opacity(&mut self, value: f32) {
+        match *self {
+            Paint::Solid { ref mut opacity, .. }
+            | Paint::LinearGradient { ref mut opacity, .. }
+            | Paint::RadialGradient { ref mut opacity, .. } => *opacity = value,
+        }
+    }
+
+    fn paint(&self) -> Paint {
+        self.clone()
+    }
+}
@@ -0,0 +1,333 @@
+use brush::{Brush, Paint};
+use canvas::Canvas;
+use brush::GradientStop;
+use direct2d::brush::Brush as D2DBrush;
+use direct2d::brush::GenericBrush;
+use direct2d::brush::GradientStop as D2DGradientStop;
+use direct2d::brush::LinearGradientBrush as D2DLinearGradientBrush;
+use direct2d::brush::RadialGradientBrush as D2DRadialGradientBrush;
+use direct2d::brush::SolidColorBrush as D2DSolidColorBrush;
+use direct2d::enums as d2d_enums;
+use direct2d::geometry as d2d_geometry;
+use direct2d::geometry::Geometry as D2DGeometry;
+use direct2d::math as d2d_math;
+use direct2d::render_target::HwndRenderTarget;
+use direct2d::stroke_style::StrokeStyle as D2DStrokeStyle;
+use direct2d::Factory;
+use direct2d::RenderTarget;
+use enums::*;
+use geometry::path::Segment;
+use geometry::{Geometry, GeometryKind, Path};
+use math::*;
+use stroke_style::StrokeStyle;
+use winapi::um::d2d1::{D2D1_COLOR_F, D2D1_ROUNDED_RECT};
+
+/// Canvas backed by a window render target.
+///
+/// Portable brushes, stroke styles and geometries are turned into Direct2D resources
+/// at draw time; solid colors reuse a single device brush, gradients are created for
+/// the call.
+pub struct D2DCanvas {
+    factory: Factory,
+    render_target: HwndRenderTarget,
+    brush: D2DSolidColorBrush,
+}
+
+impl D2DCanvas {
+    pub fn new(factory: &Factory, render_target: HwndRenderTarget) -> D2DCanvas {
+        let brush = D2DSolidColorBrush::create(&render_target).with_color(0x00_00_00).build().unwrap();
+        D2DCanvas {
+            factory: factory.clone(),
+            render_target,
+            brush,
+        }
+    }
+
+    pub fn render_target(&self) -> &HwndRenderTarget {
+        &self.render_target
+    }
+
+    pub fn render_target_mut(&mut self) -> &mut HwndRenderTarget {
+        &mut self.render_target
+    }
+}
+
+impl Canvas for D2DCanvas {
+    fn get_size(&self) -> SizeF {
+        let size = self.render_target.get_size();
+        SizeF::new(size.width, size.height)
+    }
+
+    fn clear(&mut self, color: ColorF) {
+        self.render_target.clear(color_f(color));
+    }
+
+    fn fill_geometry(&mut self, geometry: &dyn Geometry, brush: &dyn Brush) {
+        let geometry = to_geometry(&self.factory, geometry);
+        let brush = realize(&self.render_target, &mut self.brush, brush);
+        self.render_target.fill_geometry(&geometry, &brush);
+    }
+
+    fn draw_geometry(&mut self, geometry: &dyn Geometry, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        let geometry = to_geometry(&self.factory, geometry);
+        let stroke_style = to_stroke_style(&self.factory, stroke_style);
+        let brush = realize(&self.render_target, &mut self.brush, brush);
+        self.render_target.draw_geometry(&geometry, &brush, stroke_width, stroke_style.as_ref());
+    }
+
+    fn draw_line(&mut self, p0: Point2F, p1: Point2F, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        let stroke_style = to_stroke_style(&self.factory, stroke_style);
+        let brush = realize(&self.render_target, &mut self.brush, brush);
+        self.render_target.draw_line(point_2f(p0), point_2f(p1), &brush, stroke_width, stroke_style.as_ref());
+    }
+
+    fn draw_rectangle(&mut self, rect: RectF, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        let stroke_style = to_stroke_style(&self.factory, stroke_style);
+        let brush = realize(&self.render_target, &mut self.brush, brush);
+        self.render_target.draw_rectangle(rect_f(rect), &brush, stroke_width, stroke_style.as_ref());
+    }
+
+    fn fill_rectangle(&mut self, rect: RectF, brush: &dyn Brush) {
+        let brush = realize(&self.render_target, &mut self.brush, brush);
+        self.render_target.fill_rectangle(rect_f(rect), &brush);
+    }
+
+    fn draw_rounded_rectangle(&mut self, rect: RoundedRect, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        let stroke_style = to_stroke_style(&self.factory, stroke_style);
+        let brush = realize(&self.render_target, &mut self.brush, brush);
+        self.render_target.draw_rounded_rectangle(rounded_rect(rect), &brush, stroke_width, stroke_style.as_ref());
+    }
+
+    fn fill_rounded_rectangle(&mut self, rect: RoundedRect, brush: &dyn Brush) {
+        let brush = realize(&self.render_target, &mut self.brush, brush);
+        self.render_target.fill_rounded_rectangle(rounded_rect(rect), &brush);
+    }
+
+    fn draw_ellipse(&mut self, ellipse: Ellipse, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        let stroke_style = to_stroke_style(&self.factory, stroke_style);
+        let brush = realize(&self.render_target, &mut self.brush, brush);
+        self.render_target.draw_ellipse(ellipse_f(ellipse), &brush, stroke_width, stroke_style.as_ref());
+    }
+
+    fn fill_ellipse(&mut self, ellipse: Ellipse, brush: &dyn Brush) {
+        let brush = realize(&self.render_target, &mut self.brush, brush);
+        self.render_target.fill_ellipse(ellipse_f(ellipse), &brush);
+    }
+}
+
+fn realize(render_target: &HwndRenderTarget, solid: &mut D2DSolidColorBrush, brush: &dyn Brush) -> GenericBrush {
+    match brush.paint() {
+        Paint::Solid { color, opacity } => {
+            solid.set_color(&color_f(color));
+            solid.set_opacity(opacity);
+            solid.to_generic()
+        }
+        Paint::LinearGradient { start, end, ref stops, extend_mode, opacity } => {
+            let stops = gradient_stops(stops);
+            D2DLinearGradientBrush::create(render_target)
+                .with_start(point_2f(start))
+                .with_end(point_2f(end))
+                .with_stops(&stops)
+                .with_extend_mode(extend(extend_mode))
+                .with_opacity(opacity)
+                .build()
+                .unwrap()
+                .to_generic()
+        }
+        Paint::RadialGradient { center, gradient_origin_offset, radius_x, radius_y, ref stops, extend_mode, opacity } => {
+            let stops = gradient_stops(stops);
+            D2DRadialGradientBrush::create(render_target)
+                .with_center(point_2f(center))
+                .with_origin_offset(point_2f(gradient_origin_offset))
+                .with_radius(radius_x, radius_y)
+                .with_stops(&stops)
+                .with_extend_mode(extend(extend_mode))
+                .with_opacity(opacity)
+                .build()
+                .unwrap()
+                .to_generic()
+        }
+    }
+}
+
+fn gradient_stops(stops: &[GradientStop]) -> Vec<D2DGradientStop> {
+    stops.iter().map(|stop| (stop.position, color_f(stop.color)).into()).collect()
+}
+
+fn to_geometry(factory: &Factory, geometry: &dyn Geometry) -> d2d_geometry::GenericGeometry {
+    match geometry.kind() {
+        GeometryKind::Rectangle(rect) => d2d_geometry::Rectangle::create(factory, &rect_f(*rect)).unwrap().to_generic(),
+        GeometryKind::RoundedRectangle(rect) => {
+            d2d_geometry::RoundedRectangle::create(factory, &rounded_rect(*rect)).unwrap().to_generic()
+        }
+        GeometryKind::Ellipse(ellipse) => {
+            d2d_geometry::Ellipse::create::<usize>(factory, &ellipse_f(*ellipse)).unwrap().to_generic()
+        }
+        GeometryKind::Path(path) => to_path(factory, path).to_generic(),
+        GeometryKind::Group(group) => {
+            let sources: Vec<_> = group.get_source_geometries().iter().map(|source| to_path(factory, source)).collect();
+            d2d_geometry::Group::create(factory, fill_mode(group.get_fill_mode()), &sources).unwrap().to_generic()
+        }
+        GeometryKind::Transformed(transformed) => {
+            let source = to_path(factory, transformed.get_source_geometry());
+            source.transformed(&matrix_3x2_f(transformed.get_transform())).unwrap().to_generic()
+        }
+    }
+}
+
+fn to_path(factory: &Factory, path: &Path) -> d2d_geometry::Path {
+    let mut geometry = d2d_geometry::Path::create(factory).unwrap();
+    {
+        let mut builder = geometry.open().unwrap().fill_mode(fill_mode(path.get_fill_mode()));
+        for figure in path.figures() {
+            let mut d2d_figure = builder.begin_figure(point_2f(figure.start), figure_begin(figure.begin), figure_end(figure.end));
+            for segment in &figure.segments {
+                d2d_figure = match *segment {
+                    Segment::Line(point) => d2d_figure.add_line(point_2f(point)),
+                    Segment::Bezier(ref bezier) => {
+                        let bezier = d2d_math::BezierSegment::new(point_2f(bezier.point1), point_2f(bezier.point2), point_2f(bezier.point3));
+                        d2d_figure.add_bezier(&bezier)
+                    }
+                    Segment::QuadBezier(ref bezier) => {
+                        let bezier = d2d_math::QuadBezierSegment::new(point_2f(bezier.point1), point_2f(bezier.point2));
+                        d2d_figure.add_quadratic_bezier(&bezier)
+                    }
+                    Segment::Arc(ref arc) => {
+                        let arc = d2d_math::ArcSegment::new(
+                            point_2f(arc.point),
+                            d2d_math::SizeF::new(arc.size.width, arc.size.height),
+                            arc.rotation_angle,
+                            sweep_direction(arc.sweep_direction),
+                            arc_size(arc.arc_size),
+                        );
+                        d2d_figure.add_arc(&arc)
+                    }
+                };
+            }
+            builder = d2d_figure.end();
+        }
+    }
+    geometry
+}
+
+fn to_stroke_style(factory: &Factory, stroke_style: Option<&StrokeStyle>) -> Option<D2DStrokeStyle> {
+    stroke_style.map(|style| {
+        let builder = D2DStrokeStyle::create(factory)
+            .with_start_cap(cap_style(style.get_start_cap()))
+            .with_end_cap(cap_style(style.get_end_cap()))
+            .with_dash_cap(cap_style(style.get_dash_cap()))
+            .with_line_join(line_join(style.get_line_join()))
+            .with_miter_limit(style.get_miter_limit())
+            .with_dash_offset(style.get_dash_offset());
+        let builder = match style.get_dash_style() {
+            DashStyle::Custom => builder.with_dashes(style.get_dashes()),
+            dash => builder.with_dash_style(dash_style(dash)),
+        };
+        builder.build().unwrap()
+    })
+}
+
+fn point_2f(point: Point2F) -> d2d_math::Point2F {
+    d2d_math::Point2F::new(point.x, point.y)
+}
+
+fn rect_f(rect: RectF) -> d2d_math::RectF {
+    d2d_math::RectF::new(rect.left, rect.top, rect.right, rect.bottom)
+}
+
+fn rounded_rect(rect: RoundedRect) -> d2d_math::RoundedRect {
+    d2d_math::RoundedRect(D2D1_ROUNDED_RECT {
+        rect: *rect_f(rect.rect),
+        radiusX: rect.radius_x,
+        radiusY: rect.radius_y,
+    })
+}
+
+fn ellipse_f(ellipse: Ellipse) -> d2d_math::Ellipse {
+    d2d_math::Ellipse::new(point_2f(ellipse.center), ellipse.radius_x, ellipse.radius_y)
+}
+
+fn matrix_3x2_f(matrix: Matrix3x2F) -> d2d_math::Matrix3x2F {
+    d2d_math::Matrix3x2F::new(matrix.matrix)
+}
+
+fn color_f(color: ColorF) -> d2d_math::ColorF {
+    d2d_math::ColorF(D2D1_COLOR_F {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+        a: color.a,
+    })
+}
+
+fn fill_mode(mode: FillMode) -> d2d_enums::FillMode {
+    match mode {
+        FillMode::Alternate => d2d_enums::FillMode::Alternate,
+        FillMode::Winding => d2d_enums::FillMode::Winding,
+    }
+}
+
+fn figure_begin(begin: FigureBegin) -> d2d_enums::FigureBegin {
+    match begin {
+        FigureBegin::Filled => d2d_enums::FigureBegin::Filled,
+        FigureBegin::Hollow => d2d_enums::FigureBegin::Hollow,
+    }
+}
+
+fn figure_end(end: FigureEnd) -> d2d_enums::FigureEnd {
+    match end {
+        FigureEnd::Open => d2d_enums::FigureEnd::Open,
+        FigureEnd::Closed => d2d_enums::FigureEnd::Closed,
+    }
+}
+
+fn sweep_direction(direction: SweepDirection) -> d2d_enums::SweepDirection {
+    match direction {
+        SweepDirection::CounterClockwise => d2d_enums::SweepDirection::CounterClockwise,
+        SweepDirection::Clockwise => d2d_enums::SweepDirection::Clockwise,
+    }
+}
+
+fn arc_size(size: ArcSize) -> d2d_enums::ArcSize {
+    match size {
+        ArcSize::Small => d2d_enums::ArcSize::Small,
+        ArcSize::Large => d2d_enums::ArcSize::Large,
+    }
+}
+
+fn cap_style(cap: CapStyle) -> d2d_enums::CapStyle {
+    match cap {
+        CapStyle::Flat => d2d_enums::CapStyle::Flat,
+        CapStyle::Square => d2d_enums::CapStyle::Square,
+        CapStyle::Round => d2d_enums::CapStyle::Round,
+        CapStyle::Triangle => d2d_enums::CapStyle::Triangle,
+    }
+}
+
+fn line_join(join: LineJoin) -> d2d_enums::LineJoin {
+    match join {
+        LineJoin::Miter => d2d_enums::LineJoin::Miter,
+        LineJoin::Bevel => d2d_enums::LineJoin::Bevel,
+        LineJoin::Round => d2d_enums::LineJoin::Round,
+        LineJoin::MiterOrBevel => d2d_enums::LineJoin::MiterOrBevel,
+    }
+}
+
+fn dash_style(dash: DashStyle) -> d2d_enums::DashStyle {
+    match dash {
+        DashStyle::Solid => d2d_enums::DashStyle::Solid,
+        DashStyle::Dash => d2d_enums::DashStyle::Dash,
+        DashStyle::Dot => d2d_enums::DashStyle::Dot,
+        DashStyle::DashDot => d2d_enums::DashStyle::DashDot,
+        DashStyle::DashDotDot => d2d_enums::DashStyle::DashDotDot,
+        DashStyle::Custom => d2d_enums::DashStyle::Custom,
+    }
+}
+
+fn extend(mode: ExtendMode) -> d2d_enums::ExtendMode {
+    match mode {
+        ExtendMode::Clamp => d2d_enums::ExtendMode::Clamp,
+        ExtendMode::Wrap => d2d_enums::ExtendMode::Wrap,
+        ExtendMode::Mirror => d2d_enums::ExtendMode::Mirror,
+    }
+}
@@ -0,0 +1,40 @@
+use brush::Brush;
+use canvas::Canvas;
+use geometry::Geometry;
+use math::*;
+use stroke_style::StrokeStyle;
+
+/// Canvas without any pixels behind it. It only keeps count of what was drawn, which is
+/// enough to run the examples where there is no window system.
+pub struct HeadlessCanvas {
+    size: SizeF,
+    draw_calls: usize,
+}
+
+impl HeadlessCanvas {
+    pub fn new(size: SizeF) -> HeadlessCanvas {
+        HeadlessCanvas { size, draw_calls: 0 }
+    }
+
+    pub fn draw_calls(&self) -> usize {
+        self.draw_calls
+    }
+}
+
+impl Canvas for HeadlessCanvas {
+    fn get_size(&self) -> SizeF {
+        self.size
+    }
+
+    fn clear(&mut self, _color: ColorF) {
+        self.draw_calls += 1;
+    }
+
+    fn fill_geometry(&mut self, _geometry: &dyn Geometry, _brush: &dyn Brush) {
+        self.draw_calls += 1;
+    }
+
+    fn draw_geometry(&mut self, _geometry: &dyn Geometry, _brush: &dyn Brush, _stroke_width: f32, _stroke_style: Option<&StrokeStyle>) {
+        self.draw_calls += 1;
+    }
+}
@@ -0,0 +1,83 @@
+use brush::{Brush, SolidColorBrush};
+use enums::{FigureBegin, FigureEnd};
+use geometry::{self, Geometry};
+use math::*;
+use stroke_style::StrokeStyle;
+
+#[cfg(windows)]
+pub mod d2d;
+pub mod headless;
+pub mod recording;
+pub mod software;
+pub mod svg;
+
+/// Drawing surface the examples are written against.
+///
+/// Only `get_size`, `clear`, `fill_geometry` and `draw_geometry` are required; the
+/// shape helpers default to drawing the equivalent geometry, and backends with
+/// native primitives override them.
+pub trait Canvas {
+    fn get_size(&self) -> SizeF;
+
+    fn clear(&mut self, color: ColorF);
+
+    fn create_solid_color_brush(&self, color: ColorF, opacity: f32) -> SolidColorBrush {
+        SolidColorBrush::new(color, opacity)
+    }
+
+    fn fill_geometry(&mut self, geometry: &dyn Geometry, brush: &dyn Brush);
+
+    fn draw_geometry(
+        &mut self,
+        geometry: &dyn Geometry,
+        brush: &dyn Brush,
+        stroke_width: f32,
+        stroke_style: Option<&StrokeStyle>,
+    );
+
+    fn draw_line(
+        &mut self,
+        p0: Point2F,
+        p1: Point2F,
+        brush: &dyn Brush,
+        stroke_width: f32,
+        stroke_style: Option<&StrokeStyle>,
+    ) {
+        let mut line = geometry::Path::new();
+        line.open()
+            .begin_figure(p0, FigureBegin::Hollow, FigureEnd::Open)
+            .add_line(p1)
+            .end();
+        self.draw_geometry(&line, brush, stroke_width, stroke_style);
+    }
+
+    fn draw_rectangle(&mut self, rect: RectF, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        self.draw_geometry(&geometry::Rectangle::create(&rect), brush, stroke_width, stroke_style);
+    }
+
+    fn fill_rectangle(&mut self, rect: RectF, brush: &dyn Brush) {
+        self.fill_geometry(&geometry::Rectangle::create(&rect), brush);
+    }
+
+    fn draw_rounded_rectangle(
+        &mut self,
+        rect: RoundedRect,
+        brush: &dyn Brush,
+        stroke_width: f32,
+        stroke_style: Option<&StrokeStyle>,
+    ) {
+        self.draw_geometry(&geometry::RoundedRectangle::create(&rect), brush, stroke_width, stroke_style);
+    }
+
+    fn fill_rounded_rectangle(&mut self, rect: RoundedRect, brush: &dyn Brush) {
+        self.fill_geometry(&geometry::RoundedRectangle::create(&rect), brush);
+    }
+
+    fn draw_ellipse(&mut self, ellipse: Ellipse, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        self.draw_geometry(&geometry::Ellipse::create(&ellipse), brush, stroke_width, stroke_style);
+    }
+
+    fn fill_ellipse(&mut self, ellipse: Ellipse, brush: &dyn Brush) {
+        self.fill_geometry(&geometry::Ellipse::create(&ellipse), brush);
+    }
+}
@@ -0,0 +1,251 @@
+use brush::{Brush, Paint};
+use canvas::Canvas;
+use geometry::{Geometry, GeometryGroup, GeometryKind, Path, TransformedGeometry};
+use math::*;
+use serde_json;
+use stroke_style::StrokeStyle;
+
+/// Index of a geometry in `DisplayList::geometries`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeometryId(pub usize);
+
+/// Owned copy of a geometry that was drawn.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RecordedGeometry {
+    Rectangle(RectF),
+    RoundedRectangle(RoundedRect),
+    Ellipse(Ellipse),
+    Path(Path),
+    Group(GeometryGroup),
+    Transformed(TransformedGeometry),
+}
+
+impl<'a> From<GeometryKind<'a>> for RecordedGeometry {
+    fn from(kind: GeometryKind<'a>) -> RecordedGeometry {
+        match kind {
+            GeometryKind::Rectangle(rect) => RecordedGeometry::Rectangle(*rect),
+            GeometryKind::RoundedRectangle(rect) => RecordedGeometry::RoundedRectangle(*rect),
+            GeometryKind::Ellipse(ellipse) => RecordedGeometry::Ellipse(*ellipse),
+            GeometryKind::Path(path) => RecordedGeometry::Path(path.clone()),
+            GeometryKind::Group(group) => RecordedGeometry::Group(group.clone()),
+            GeometryKind::Transformed(transformed) => RecordedGeometry::Transformed(transformed.clone()),
+        }
+    }
+}
+
+impl Geometry for RecordedGeometry {
+    fn kind(&self) -> GeometryKind<'_> {
+        match *self {
+            RecordedGeometry::Rectangle(ref rect) => GeometryKind::Rectangle(rect),
+            RecordedGeometry::RoundedRectangle(ref rect) => GeometryKind::RoundedRectangle(rect),
+            RecordedGeometry::Ellipse(ref ellipse) => GeometryKind::Ellipse(ellipse),
+            RecordedGeometry::Path(ref path) => GeometryKind::Path(path),
+            RecordedGeometry::Group(ref group) => GeometryKind::Group(group),
+            RecordedGeometry::Transformed(ref transformed) => GeometryKind::Transformed(transformed),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DrawCommand {
+    Clear {
+        color: ColorF,
+    },
+    FillGeometry {
+        geometry: GeometryId,
+        brush: Paint,
+    },
+    DrawGeometry {
+        geometry: GeometryId,
+        brush: Paint,
+        stroke_width: f32,
+        stroke_style: Option<StrokeStyle>,
+    },
+}
+
+/// A frame captured by `RecordingCanvas`.
+///
+/// Geometries are stored once and referenced by the commands, so filling and then
+/// stroking the same shape records it a single time.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DisplayList {
+    pub size: SizeF,
+    pub geometries: Vec<RecordedGeometry>,
+    pub commands: Vec<DrawCommand>,
+}
+
+impl DisplayList {
+    pub fn geometry(&self, id: GeometryId) -> &RecordedGeometry {
+        &self.geometries[id.0]
+    }
+
+    /// Issues the recorded calls, in order, against another canvas.
+    pub fn replay(&self, canvas: &mut dyn Canvas) {
+        for command in &self.commands {
+            match *command {
+                DrawCommand::Clear { color } => canvas.clear(color),
+                DrawCommand::FillGeometry { geometry, ref brush } => canvas.fill_geometry(self.geometry(geometry), brush),
+                DrawCommand::DrawGeometry { geometry, ref brush, stroke_width, ref stroke_style } => {
+                    canvas.draw_geometry(self.geometry(geometry), brush, stroke_width, stroke_style.as_ref())
+                }
+            }
+        }
+    }
+
+    /// The list as JSON, which has no way of writing infinities and NaNs: lists that
+    /// hold any are refused rather than written in a form `from_json` rejects.
+    pub fn to_json(&self) -> Result<String, String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        match DisplayList::from_json(&json) {
+            Ok(_) => Ok(json),
+            Err(e) => Err(format!("display list has a value that is not finite: {}", e)),
+        }
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<DisplayList> {
+        serde_json::from_str(json)
+    }
+
+    fn intern(&mut self, geometry: &dyn Geometry) -> GeometryId {
+        let geometry = RecordedGeometry::from(geometry.kind());
+        let index = match self.geometries.iter().position(|recorded| *recorded == geometry) {
+            Some(index) => index,
+            None => {
+                self.geometries.push(geometry);
+                self.geometries.len() - 1
+            }
+        };
+        GeometryId(index)
+    }
+}
+
+/// Canvas that draws nothing and records every call into a `DisplayList`.
+pub struct RecordingCanvas {
+    list: DisplayList,
+}
+
+impl RecordingCanvas {
+    pub fn new(size: SizeF) -> RecordingCanvas {
+        RecordingCanvas {
+            list: DisplayList {
+                size,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn display_list(&self) -> &DisplayList {
+        &self.list
+    }
+
+    pub fn into_display_list(self) -> DisplayList {
+        self.list
+    }
+}
+
+impl Canvas for RecordingCanvas {
+    fn get_size(&self) -> SizeF {
+        self.list.size
+    }
+
+    fn clear(&mut self, color: ColorF) {
+        self.list.commands.push(DrawCommand::Clear { color });
+    }
+
+    fn fill_geometry(&mut self, geometry: &dyn Geometry, brush: &dyn Brush) {
+        let geometry = self.list.intern(geometry);
+        self.list.commands.push(DrawCommand::FillGeometry { geometry, brush: brush.paint() });
+    }
+
+    fn draw_geometry(&mut self, geometry: &dyn Geometry, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        let geometry = self.list.intern(geometry);
+        self.list.commands.push(DrawCommand::DrawGeometry {
+            geometry,
+            brush: brush.paint(),
+            stroke_width,
+            stroke_style: stroke_style.cloned(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use brush::{LinearGradientBrush, SolidColorBrush};
+    use canvas::software::SoftwareCanvas;
+    use enums::{DashStyle, FigureBegin, FigureEnd};
+    use geometry::{Ellipse as EllipseGeometry, Rectangle};
+
+    fn draw(canvas: &mut dyn Canvas) {
+        let red = SolidColorBrush::new(ColorF::uint_rgb(0xFF0000, 1.0), 0.5);
+        let gradient = LinearGradientBrush::create(canvas)
+            .with_start((0.0, 0.0).into())
+            .with_end((40.0, 0.0).into())
+            .with_stop((0.0, ColorF::uint_rgb(0x00FF00, 1.0)))
+            .with_stop((1.0, ColorF::uint_rgb(0x0000FF, 1.0)))
+            .build();
+        let dashed = StrokeStyle::create().with_dash_style(DashStyle::Dash).build();
+        let mut triangle = Path::new();
+        triangle.open()
+            .begin_figure((5.0, 35.0), FigureBegin::Filled, FigureEnd::Closed)
+            .add_lines(&[(20.0, 5.0).into(), (35.0, 35.0).into()])
+            .end();
+        canvas.clear(ColorF::uint_rgb(0xFFFFFF, 1.0));
+        canvas.fill_geometry(&triangle, &gradient);
+        canvas.draw_geometry(&triangle, &red, 2.0, Some(&dashed));
+        canvas.fill_ellipse(::math::Ellipse::new((20.0, 20.0), 8.0, 4.0), &red);
+    }
+
+    fn record() -> DisplayList {
+        let mut canvas = RecordingCanvas::new(SizeF::new(40.0, 40.0));
+        draw(&mut canvas);
+        canvas.into_display_list()
+    }
+
+    #[test]
+    fn replay_matches_drawing() {
+        let list = record();
+        let mut again = RecordingCanvas::new(list.size);
+        list.replay(&mut again);
+        assert_eq!(*again.display_list(), list);
+
+        let (mut direct, mut replayed) = (SoftwareCanvas::new(40, 40), SoftwareCanvas::new(40, 40));
+        draw(&mut direct);
+        list.replay(&mut replayed);
+        assert!(direct.framebuffer() == replayed.framebuffer());
+    }
+
+    #[test]
+    fn geometries_are_stored_once() {
+        let list = record();
+        assert_eq!(list.geometries.len(), 2);
+        assert_eq!(list.commands.len(), 4);
+        match (&list.commands[1], &list.commands[2]) {
+            (&DrawCommand::FillGeometry { geometry: filled, .. }, &DrawCommand::DrawGeometry { geometry: stroked, .. }) => {
+                assert_eq!((filled, stroked), (GeometryId(0), GeometryId(0)));
+            }
+            commands => panic!("unexpected commands {:?}", commands),
+        }
+
+        // Equal shapes share an entry even when they are different objects.
+        let mut canvas = RecordingCanvas::new(SizeF::new(40.0, 40.0));
+        let black = SolidColorBrush::new(ColorF::uint_rgb(0x000000, 1.0), 1.0);
+        canvas.fill_geometry(&Rectangle::create(&RectF::new(0.0, 0.0, 10.0, 10.0)), &black);
+        canvas.fill_rectangle(RectF::new(0.0, 0.0, 10.0, 10.0), &black);
+        canvas.fill_geometry(&EllipseGeometry::create(&::math::Ellipse::new((5.0, 5.0), 5.0, 5.0)), &black);
+        assert_eq!(canvas.display_list().geometries.len(), 2);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let list = record();
+        let json = list.to_json().unwrap();
+        assert_eq!(DisplayList::from_json(&json).unwrap(), list);
+
+        let mut canvas = RecordingCanvas::new(SizeF::new(40.0, 40.0));
+        let black = SolidColorBrush::new(ColorF::uint_rgb(0x000000, 1.0), 1.0);
+        canvas.fill_rectangle(RectF::new(0.0, 0.0, f32::INFINITY, 10.0), &black);
+        let error = canvas.display_list().to_json().unwrap_err();
+        assert!(error.starts_with("display list has a value that is not finite: "), "{}", error);
+    }
+}
@@ -0,0 +1,127 @@
+use math::ColorF;
+use png;
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+
+/// Straight (non-premultiplied) RGBA image, 8 bits per channel, rows top to bottom.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Framebuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Framebuffer {
+    pub fn new(width: u32, height: u32) -> Framebuffer {
+        Framebuffer {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = self.index(x, y);
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]]
+    }
+
+    pub fn set_pixel(&mut self, x: u32, y: u32, rgba: [u8; 4]) {
+        let i = self.index(x, y);
+        self.pixels[i..i + 4].copy_from_slice(&rgba);
+    }
+
+    pub fn fill(&mut self, color: ColorF) {
+        let rgba = [to_u8(color.r), to_u8(color.g), to_u8(color.b), to_u8(color.a)];
+        for pixel in self.pixels.chunks_mut(4) {
+            pixel.copy_from_slice(&rgba);
+        }
+    }
+
+    /// Composites `color` over the pixel with the given extra `alpha` (coverage times opacity).
+    pub fn blend(&mut self, x: u32, y: u32, color: ColorF, alpha: f32) {
+        let src_a = color.a * alpha;
+        if src_a <= 0.0 {
+            return;
+        }
+        let i = self.index(x, y);
+        let dst_a = f32::from(self.pixels[i + 3]) / 255.0;
+        let out_a = src_a + dst_a * (1.0 - src_a);
+        let mix = |src: f32, dst: u8| (src * src_a + f32::from(dst) / 255.0 * dst_a * (1.0 - src_a)) / out_a;
+        self.pixels[i] = to_u8(mix(color.r, self.pixels[i]));
+        self.pixels[i + 1] = to_u8(mix(color.g, self.pixels[i + 1]));
+        self.pixels[i + 2] = to_u8(mix(color.b, self.pixels[i + 2]));
+        self.pixels[i + 3] = to_u8(out_a);
+    }
+
+    /// Reads an 8-bit RGBA PNG, as written by `save_png`.
+    pub fn load_png(path: &Path) -> io::Result<Framebuffer> {
+        let decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        let mut reader = decoder.read_info()?;
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels)?;
+        if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "expected an 8-bit RGBA image"));
+        }
+        pixels.truncate(info.buffer_size());
+        Ok(Framebuffer {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    pub fn save_png(&self, path: &Path) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blends_source_over() {
+        // Premultiplied, half transparent red over half transparent blue is
+        // (0.5, 0, 0) + (0, 0, 0.25) with alpha 0.5 + 0.25, stored straight; the
+        // destination alpha of 128 is a little over a half.
+        let mut framebuffer = Framebuffer::new(1, 1);
+        framebuffer.set_pixel(0, 0, [0, 0, 255, 128]);
+        framebuffer.blend(0, 0, ColorF::uint_rgb(0xFF0000, 1.0), 0.5);
+        assert_eq!(framebuffer.pixel(0, 0), [170, 0, 85, 192]);
+
+        // Coverage scales an opaque source, and nothing blends over nothing.
+        framebuffer.fill(ColorF::uint_rgb(0x000000, 1.0));
+        framebuffer.blend(0, 0, ColorF::uint_rgb(0xFFFFFF, 1.0), 0.25);
+        assert_eq!(framebuffer.pixel(0, 0), [64, 64, 64, 255]);
+        framebuffer.blend(0, 0, ColorF::uint_rgb(0xFFFFFF, 0.0), 1.0);
+        assert_eq!(framebuffer.pixel(0, 0), [64, 64, 64, 255]);
+    }
+}
@@ -0,0 +1,84 @@
+use brush::Brush;
+use canvas::Canvas;
+use enums::FillMode;
+use geometry::flatten::{self, DEFAULT_FLATTENING_TOLERANCE};
+use geometry::widen;
+use geometry::Geometry;
+use math::*;
+use stroke_style::StrokeStyle;
+
+pub mod framebuffer;
+pub mod rasterizer;
+pub mod shader;
+
+pub use self::framebuffer::Framebuffer;
+
+use self::rasterizer::{FillRule, Rasterizer};
+use self::shader::Shader;
+
+/// Canvas that rasterizes on the CPU into an RGBA framebuffer.
+///
+/// Fills follow the geometry's fill mode, hollow figures are skipped and open figures
+/// are closed implicitly. Everything is anti-aliased.
+pub struct SoftwareCanvas {
+    framebuffer: Framebuffer,
+}
+
+impl SoftwareCanvas {
+    pub fn new(width: u32, height: u32) -> SoftwareCanvas {
+        SoftwareCanvas {
+            framebuffer: Framebuffer::new(width, height),
+        }
+    }
+
+    pub fn framebuffer(&self) -> &Framebuffer {
+        &self.framebuffer
+    }
+
+    pub fn into_framebuffer(self) -> Framebuffer {
+        self.framebuffer
+    }
+
+    fn paint<'a, I>(&mut self, polygons: I, fill_rule: FillRule, brush: &dyn Brush)
+    where
+        I: IntoIterator<Item = &'a [Point2F]>,
+    {
+        let mut rasterizer = Rasterizer::new(self.framebuffer.width(), self.framebuffer.height());
+        for polygon in polygons {
+            rasterizer.add_polygon(polygon);
+        }
+        let framebuffer = &mut self.framebuffer;
+        let (shader, opacity) = Shader::new(&brush.paint());
+        rasterizer.rasterize(fill_rule, |x, y, coverage| {
+            framebuffer.blend(x, y, shader.color_at(x, y), coverage * opacity)
+        });
+    }
+}
+
+impl Canvas for SoftwareCanvas {
+    fn get_size(&self) -> SizeF {
+        SizeF::new(self.framebuffer.width() as f32, self.framebuffer.height() as f32)
+    }
+
+    fn clear(&mut self, color: ColorF) {
+        self.framebuffer.fill(color);
+    }
+
+    fn fill_geometry(&mut self, geometry: &dyn Geometry, brush: &dyn Brush) {
+        let polylines = flatten::flatten(geometry, DEFAULT_FLATTENING_TOLERANCE);
+        let polygons = polylines.iter().filter(|polyline| polyline.filled).map(|polyline| &polyline.points[..]);
+        let fill_rule = match geometry.get_fill_mode() {
+            FillMode::Alternate => FillRule::EvenOdd,
+            FillMode::Winding => FillRule::NonZero,
+        };
+        self.paint(polygons, fill_rule, brush);
+    }
+
+    fn draw_geometry(&mut self, geometry: &dyn Geometry, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        let default_style = StrokeStyle::default();
+        let style = stroke_style.unwrap_or(&default_style);
+        let polylines = flatten::flatten(geometry, DEFAULT_FLATTENING_TOLERANCE);
+        let polygons = widen::outline(&polylines, stroke_width, style, DEFAULT_FLATTENING_TOLERANCE);
+        self.paint(polygons.iter().map(|polygon| &polygon[..]), FillRule::NonZero, brush);
+    }
+}
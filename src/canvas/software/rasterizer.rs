@@ -0,0 +1,210 @@
+use math::Point2F;
+
+use std::cmp::Ordering;
+
+/// Sub-scanlines sampled per pixel row. Horizontal coverage is computed exactly.
+const SUBSAMPLES: usize = 16;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    EvenOdd,
+    NonZero,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: f32) -> f32 {
+        self.x0 + (y - self.y0) * (self.x1 - self.x0) / (self.y1 - self.y0)
+    }
+}
+
+/// Scanline rasterizer producing anti-aliased coverage for a set of closed polygons.
+pub struct Rasterizer {
+    width: u32,
+    height: u32,
+    edges: Vec<Edge>,
+}
+
+impl Rasterizer {
+    pub fn new(width: u32, height: u32) -> Rasterizer {
+        Rasterizer {
+            width,
+            height,
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds a polygon; the last point connects back to the first.
+    pub fn add_polygon(&mut self, points: &[Point2F]) {
+        if points.len() < 3 {
+            return;
+        }
+        for (i, p0) in points.iter().enumerate() {
+            let p1 = points[(i + 1) % points.len()];
+            self.add_edge(*p0, p1);
+        }
+    }
+
+    fn add_edge(&mut self, p0: Point2F, p1: Point2F) {
+        if p0.y == p1.y || !(p0.x.is_finite() && p0.y.is_finite() && p1.x.is_finite() && p1.y.is_finite()) {
+            return;
+        }
+        let (top, bottom, winding) = if p0.y < p1.y { (p0, p1, 1) } else { (p1, p0, -1) };
+        self.edges.push(Edge {
+            x0: top.x,
+            y0: top.y,
+            x1: bottom.x,
+            y1: bottom.y,
+            winding,
+        });
+    }
+
+    /// Calls `blend(x, y, coverage)` for every pixel with non zero coverage.
+    pub fn rasterize<F: FnMut(u32, u32, f32)>(mut self, fill_rule: FillRule, mut blend: F) {
+        if self.edges.is_empty() || self.width == 0 || self.height == 0 {
+            return;
+        }
+        self.edges.sort_by(|a, b| a.y0.partial_cmp(&b.y0).unwrap_or(Ordering::Equal));
+        let y_min = self.edges[0].y0.max(0.0).floor() as u32;
+        let y_max = self.edges.iter().fold(0.0f32, |max, edge| max.max(edge.y1)).ceil().min(self.height as f32) as u32;
+
+        let width = self.width as usize;
+        let mut deltas = vec![0.0f32; width + 2];
+        let mut active: Vec<Edge> = Vec::new();
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        let mut next_edge = 0;
+        let weight = 1.0 / SUBSAMPLES as f32;
+
+        for y in y_min..y_max {
+            let row_top = y as f32;
+            let row_bottom = row_top + 1.0;
+            active.retain(|edge| edge.y1 > row_top);
+            while next_edge < self.edges.len() && self.edges[next_edge].y0 < row_bottom {
+                if self.edges[next_edge].y1 > row_top {
+                    active.push(self.edges[next_edge]);
+                }
+                next_edge += 1;
+            }
+            if active.is_empty() {
+                continue;
+            }
+
+            let (mut touched_min, mut touched_max) = (width, 0);
+            for sample in 0..SUBSAMPLES {
+                let sample_y = row_top + (sample as f32 + 0.5) * weight;
+                crossings.clear();
+                let spanning = active.iter().filter(|edge| edge.y0 <= sample_y && sample_y < edge.y1);
+                crossings.extend(spanning.map(|edge| (edge.x_at(sample_y), edge.winding)));
+                crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+                let mut winding = 0;
+                for pair in 0..crossings.len() {
+                    winding += crossings[pair].1;
+                    let inside = match fill_rule {
+                        FillRule::EvenOdd => winding % 2 != 0,
+                        FillRule::NonZero => winding != 0,
+                    };
+                    if inside && pair + 1 < crossings.len() {
+                        if let Some((start, end)) = add_span(&mut deltas, crossings[pair].0, crossings[pair + 1].0, weight) {
+                            touched_min = touched_min.min(start);
+                            touched_max = touched_max.max(end);
+                        }
+                    }
+                }
+            }
+
+            if touched_min <= touched_max {
+                let mut coverage = 0.0;
+                for (x, delta) in deltas.iter_mut().enumerate().take(touched_max + 2).skip(touched_min) {
+                    coverage += *delta;
+                    *delta = 0.0;
+                    if x < width && coverage > 1.0 / 512.0 {
+                        blend(x as u32, y, coverage.min(1.0));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Accumulates the coverage of `[x0, x1)` into the delta row, returning the touched cells.
+fn add_span(deltas: &mut [f32], x0: f32, x1: f32, weight: f32) -> Option<(usize, usize)> {
+    let width = (deltas.len() - 2) as f32;
+    let (x0, x1) = (x0.max(0.0).min(width), x1.max(0.0).min(width));
+    if x1 <= x0 {
+        return None;
+    }
+    let (i0, i1) = (x0.floor(), x1.floor());
+    let (f0, f1) = (x0 - i0, x1 - i1);
+    let (i0, i1) = (i0 as usize, i1 as usize);
+    deltas[i0] += (1.0 - f0) * weight;
+    deltas[i0 + 1] += f0 * weight;
+    deltas[i1] -= (1.0 - f1) * weight;
+    deltas[i1 + 1] -= f1 * weight;
+    Some((i0, i1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Coverage of every touched pixel, row by row.
+    fn coverage(rasterizer: Rasterizer, fill_rule: FillRule) -> Vec<(u32, u32, f32)> {
+        let mut pixels = Vec::new();
+        rasterizer.rasterize(fill_rule, |x, y, coverage| pixels.push((x, y, coverage)));
+        pixels
+    }
+
+    fn points(points: &[(f32, f32)]) -> Vec<Point2F> {
+        points.iter().map(|&point| point.into()).collect()
+    }
+
+    #[test]
+    fn partial_pixels_get_partial_coverage() {
+        let mut rasterizer = Rasterizer::new(4, 2);
+        rasterizer.add_polygon(&points(&[(0.0, 0.0), (2.5, 0.0), (2.5, 1.0), (0.0, 1.0)]));
+        let pixels = coverage(rasterizer, FillRule::NonZero);
+        assert_eq!(pixels.iter().map(|&(x, y, _)| (x, y)).collect::<Vec<_>>(), vec![(0, 0), (1, 0), (2, 0)]);
+        assert!((pixels[0].2 - 1.0).abs() < 1e-5 && (pixels[1].2 - 1.0).abs() < 1e-5);
+        assert!((pixels[2].2 - 0.5).abs() < 1e-5);
+
+        // Vertically, coverage counts the sub-scanlines inside.
+        let mut rasterizer = Rasterizer::new(1, 1);
+        rasterizer.add_polygon(&points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 0.25), (0.0, 0.25)]));
+        let pixels = coverage(rasterizer, FillRule::NonZero);
+        assert!((pixels[0].2 - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn fill_rules_differ_where_the_shape_overlaps_itself() {
+        // One outline going twice around columns 2 to 6.
+        let outline = points(&[(0.0, 0.0), (6.0, 0.0), (6.0, 1.0), (2.0, 1.0), (2.0, 0.0), (8.0, 0.0), (8.0, 1.0), (0.0, 1.0)]);
+        let columns = |fill_rule| {
+            let mut rasterizer = Rasterizer::new(8, 1);
+            rasterizer.add_polygon(&outline);
+            coverage(rasterizer, fill_rule).into_iter().filter(|&(_, _, coverage)| coverage > 0.99).map(|(x, _, _)| x).collect::<Vec<_>>()
+        };
+        assert_eq!(columns(FillRule::EvenOdd), vec![0, 1, 6, 7]);
+        assert_eq!(columns(FillRule::NonZero), (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn clips_to_the_raster() {
+        let mut rasterizer = Rasterizer::new(4, 4);
+        rasterizer.add_polygon(&points(&[(-10.0, -10.0), (2.0, -10.0), (2.0, 10.0), (-10.0, 10.0)]));
+        // Non-finite points are dropped rather than poisoning the edge list.
+        rasterizer.add_polygon(&points(&[(0.0, 0.0), (f32::NAN, 1.0), (1.0, 1.0)]));
+        let pixels = coverage(rasterizer, FillRule::NonZero);
+        let touched: Vec<(u32, u32)> = pixels.iter().map(|&(x, y, _)| (x, y)).collect();
+        assert_eq!(touched, vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2), (0, 3), (1, 3)]);
+        assert!(pixels.iter().all(|&(_, _, coverage)| (coverage - 1.0).abs() < 1e-5));
+    }
+}
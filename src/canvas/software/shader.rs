@@ -0,0 +1,157 @@
+use brush::{GradientStop, Paint};
+use enums::ExtendMode;
+use math::{ColorF, Point2F};
+
+/// Per-pixel color source for a `Paint`.
+pub enum Shader {
+    Solid(ColorF),
+    Linear {
+        start: Point2F,
+        /// `end - start` divided by its squared length, so a dot product gives `t`.
+        axis: (f32, f32),
+        gradient: Gradient,
+    },
+    Radial {
+        center: Point2F,
+        radius: (f32, f32),
+        /// Gradient origin relative to the center, in unit circle space.
+        focus: (f32, f32),
+        gradient: Gradient,
+    },
+}
+
+pub struct Gradient {
+    stops: Vec<GradientStop>,
+    extend_mode: ExtendMode,
+}
+
+impl Shader {
+    /// Returns the shader and the opacity it is applied with.
+    pub fn new(paint: &Paint) -> (Shader, f32) {
+        match *paint {
+            Paint::Solid { color, opacity } => (Shader::Solid(color), opacity),
+            Paint::LinearGradient { start, end, ref stops, extend_mode, opacity } => {
+                let (dx, dy) = (end.x - start.x, end.y - start.y);
+                let length = dx * dx + dy * dy;
+                let axis = if length > 0.0 { (dx / length, dy / length) } else { (0.0, 0.0) };
+                let gradient = Gradient::new(stops, extend_mode);
+                (Shader::Linear { start, axis, gradient }, opacity)
+            }
+            Paint::RadialGradient { center, gradient_origin_offset, radius_x, radius_y, ref stops, extend_mode, opacity } => {
+                let radius = (radius_x.abs(), radius_y.abs());
+                let mut focus = if radius.0 > 0.0 && radius.1 > 0.0 {
+                    (gradient_origin_offset.x / radius.0, gradient_origin_offset.y / radius.1)
+                } else {
+                    (0.0, 0.0)
+                };
+                // Like Direct2D, keep the origin inside the ellipse.
+                let distance = (focus.0 * focus.0 + focus.1 * focus.1).sqrt();
+                if distance > 0.999 {
+                    focus = (focus.0 * 0.999 / distance, focus.1 * 0.999 / distance);
+                }
+                let gradient = Gradient::new(stops, extend_mode);
+                (Shader::Radial { center, radius, focus, gradient }, opacity)
+            }
+        }
+    }
+
+    /// Color at the center of pixel `(x, y)`.
+    pub fn color_at(&self, x: u32, y: u32) -> ColorF {
+        let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+        match *self {
+            Shader::Solid(color) => color,
+            Shader::Linear { start, axis, ref gradient } => {
+                gradient.color_at((px - start.x) * axis.0 + (py - start.y) * axis.1)
+            }
+            Shader::Radial { center, radius, focus, ref gradient } => {
+                if radius.0 == 0.0 || radius.1 == 0.0 {
+                    return gradient.color_at(1.0);
+                }
+                let point = ((px - center.x) / radius.0, (py - center.y) / radius.1);
+                let d = (point.0 - focus.0, point.1 - focus.1);
+                let dd = d.0 * d.0 + d.1 * d.1;
+                if dd == 0.0 {
+                    return gradient.color_at(0.0);
+                }
+                // Where the ray from the focus through the point leaves the unit circle.
+                let fd = focus.0 * d.0 + focus.1 * d.1;
+                let ff = focus.0 * focus.0 + focus.1 * focus.1;
+                let s = (-fd + (fd * fd - dd * (ff - 1.0)).max(0.0).sqrt()) / dd;
+                gradient.color_at(1.0 / s)
+            }
+        }
+    }
+}
+
+impl Gradient {
+    fn new(stops: &[GradientStop], extend_mode: ExtendMode) -> Gradient {
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(::std::cmp::Ordering::Equal));
+        Gradient { stops, extend_mode }
+    }
+
+    fn color_at(&self, t: f32) -> ColorF {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return ColorF::default(),
+        };
+        // Past a focus on the rim `t` is infinite, or NaN, which no extend mode can
+        // bring back; those points take the color at an end.
+        if !t.is_finite() {
+            return if t == f32::NEG_INFINITY { first.color } else { last.color };
+        }
+        let t = match self.extend_mode {
+            ExtendMode::Clamp => t,
+            ExtendMode::Wrap => t - t.floor(),
+            ExtendMode::Mirror => {
+                let t = t.abs() % 2.0;
+                if t > 1.0 { 2.0 - t } else { t }
+            }
+        };
+        if t <= first.position {
+            return first.color;
+        }
+        if t >= last.position {
+            return last.color;
+        }
+        // Stops at NaN positions compare with nothing, so none may come after `t`.
+        let next = match self.stops.iter().position(|stop| stop.position > t) {
+            Some(next) => next,
+            None => return last.color,
+        };
+        let (a, b) = (&self.stops[next - 1], &self.stops[next]);
+        let f = (t - a.position) / (b.position - a.position);
+        let mix = |a: f32, b: f32| a + (b - a) * f;
+        ColorF {
+            r: mix(a.color.r, b.color.r),
+            g: mix(a.color.g, b.color.g),
+            b: mix(a.color.b, b.color.b),
+            a: mix(a.color.a, b.color.a),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_that_are_not_finite_take_an_end_stop() {
+        let (black, white) = (ColorF::uint_rgb(0x000000, 1.0), ColorF::uint_rgb(0xFFFFFF, 1.0));
+        let stops = [GradientStop { position: 0.0, color: black }, GradientStop { position: 1.0, color: white }];
+        for &extend_mode in &[ExtendMode::Clamp, ExtendMode::Wrap, ExtendMode::Mirror] {
+            let gradient = Gradient::new(&stops, extend_mode);
+            assert_eq!(gradient.color_at(f32::INFINITY), white);
+            assert_eq!(gradient.color_at(f32::NAN), white);
+            assert_eq!(gradient.color_at(f32::NEG_INFINITY), black);
+        }
+        // A stop at a NaN position is never reached from either side.
+        let broken = [GradientStop { position: 0.0, color: black }, GradientStop { position: f32::NAN, color: white }];
+        assert_eq!(Gradient::new(&broken, ExtendMode::Clamp).color_at(0.5), white);
+
+        // With the focus on the rim, the points beyond it are infinitely far along.
+        let gradient = Gradient::new(&stops, ExtendMode::Wrap);
+        let shader = Shader::Radial { center: Point2F::new(0.0, 0.0), radius: (10.0, 10.0), focus: (1.0, 0.0), gradient };
+        assert_eq!(shader.color_at(20, 0), white);
+    }
+}
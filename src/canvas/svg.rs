@@ -0,0 +1,350 @@
+use brush::{Brush, Paint};
+use canvas::Canvas;
+use enums::*;
+use geometry::path::Segment;
+use geometry::{Geometry, GeometryKind, Path};
+use math::*;
+use stroke_style::StrokeStyle;
+
+use std::fmt::Write;
+
+/// Canvas that writes every draw call as an element of a standalone SVG document.
+///
+/// Rectangles, rounded rectangles and ellipses become their SVG counterparts and
+/// paths map one to one onto path data commands.
+pub struct SvgCanvas {
+    size: SizeF,
+    body: String,
+    gradients: usize,
+}
+
+impl SvgCanvas {
+    pub fn new(size: SizeF) -> SvgCanvas {
+        SvgCanvas {
+            size,
+            body: String::new(),
+            gradients: 0,
+        }
+    }
+
+    pub fn document(&self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n{body}</svg>\n",
+            w = self.size.width,
+            h = self.size.height,
+            body = self.body,
+        )
+    }
+
+    /// Attributes painting `property` with the brush. Gradients are defined in place,
+    /// right before the element using them.
+    fn paint_attributes(&mut self, property: &str, brush: &dyn Brush) -> String {
+        let paint = brush.paint();
+        let (stops, extend_mode, opacity) = match paint {
+            Paint::Solid { color, opacity } => {
+                return format!("{p}=\"{}\" {p}-opacity=\"{}\"", hex(color), color.a * opacity, p = property);
+            }
+            Paint::LinearGradient { ref stops, extend_mode, opacity, .. }
+            | Paint::RadialGradient { ref stops, extend_mode, opacity, .. } => (stops, extend_mode, opacity),
+        };
+        self.gradients += 1;
+        let id = format!("gradient{}", self.gradients);
+        let spread = match extend_mode {
+            ExtendMode::Clamp => "pad",
+            ExtendMode::Wrap => "repeat",
+            ExtendMode::Mirror => "reflect",
+        };
+        let (element, geometry) = match paint {
+            Paint::LinearGradient { start, end, .. } => (
+                "linearGradient",
+                format!("x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"", start.x, start.y, end.x, end.y),
+            ),
+            Paint::RadialGradient { center, gradient_origin_offset, radius_x, radius_y, .. } => {
+                // SVG gradients are circular, so the ellipse is a circle scaled vertically.
+                let scale = if radius_x != 0.0 { radius_y / radius_x } else { 1.0 };
+                let focus_y = if scale != 0.0 { gradient_origin_offset.y / scale } else { 0.0 };
+                (
+                    "radialGradient",
+                    format!(
+                        "cx=\"{cx}\" cy=\"{cy}\" r=\"{}\" fx=\"{}\" fy=\"{}\" \
+                         gradientTransform=\"translate({cx} {cy}) scale(1 {}) translate({} {})\"",
+                        radius_x,
+                        center.x + gradient_origin_offset.x,
+                        center.y + focus_y,
+                        scale,
+                        -center.x,
+                        -center.y,
+                        cx = center.x,
+                        cy = center.y
+                    ),
+                )
+            }
+            Paint::Solid { .. } => unreachable!(),
+        };
+        let _ = writeln!(
+            self.body,
+            "<{} id=\"{}\" gradientUnits=\"userSpaceOnUse\" spreadMethod=\"{}\" {}>",
+            element, id, spread, geometry
+        );
+        for stop in stops {
+            let _ = writeln!(
+                self.body,
+                "  <stop offset=\"{}\" stop-color=\"{}\" stop-opacity=\"{}\"/>",
+                stop.position,
+                hex(stop.color),
+                stop.color.a
+            );
+        }
+        let _ = writeln!(self.body, "</{}>", element);
+        format!("{p}=\"url(#{})\" {p}-opacity=\"{}\"", id, opacity, p = property)
+    }
+
+    fn element(&mut self, geometry: &dyn Geometry, filled_only: bool, attributes: &str) {
+        match geometry.kind() {
+            GeometryKind::Rectangle(rect) => {
+                let _ = writeln!(self.body, "<rect {} {}/>", rect_attributes(rect), attributes);
+            }
+            GeometryKind::RoundedRectangle(rounded) => {
+                let _ = writeln!(
+                    self.body,
+                    "<rect {} rx=\"{}\" ry=\"{}\" {}/>",
+                    rect_attributes(&rounded.rect),
+                    rounded.radius_x,
+                    rounded.radius_y,
+                    attributes
+                );
+            }
+            GeometryKind::Ellipse(ellipse) => {
+                let _ = writeln!(
+                    self.body,
+                    "<ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\" {}/>",
+                    ellipse.center.x,
+                    ellipse.center.y,
+                    ellipse.radius_x.abs(),
+                    ellipse.radius_y.abs(),
+                    attributes
+                );
+            }
+            GeometryKind::Path(path) => {
+                let data = path_data(path, filled_only);
+                if !data.is_empty() {
+                    let _ = writeln!(self.body, "<path d=\"{}\" {}/>", data, attributes);
+                }
+            }
+            // Transforms are applied to the outline only, so strokes keep their width.
+            GeometryKind::Group(_) | GeometryKind::Transformed(_) => {
+                self.element(&Path::from_geometry(geometry), filled_only, attributes)
+            }
+        }
+    }
+}
+
+impl Canvas for SvgCanvas {
+    fn get_size(&self) -> SizeF {
+        self.size
+    }
+
+    /// Everything drawn so far is covered, so it is dropped from the document.
+    fn clear(&mut self, color: ColorF) {
+        self.body.clear();
+        let _ = writeln!(
+            self.body,
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\" fill-opacity=\"{}\"/>",
+            hex(color),
+            color.a
+        );
+    }
+
+    fn fill_geometry(&mut self, geometry: &dyn Geometry, brush: &dyn Brush) {
+        let rule = match geometry.get_fill_mode() {
+            FillMode::Alternate => "evenodd",
+            FillMode::Winding => "nonzero",
+        };
+        let attributes = format!("{} fill-rule=\"{}\"", self.paint_attributes("fill", brush), rule);
+        self.element(geometry, true, &attributes);
+    }
+
+    fn draw_geometry(&mut self, geometry: &dyn Geometry, brush: &dyn Brush, stroke_width: f32, stroke_style: Option<&StrokeStyle>) {
+        let default_style = StrokeStyle::default();
+        let style = stroke_style.unwrap_or(&default_style);
+        let attributes = format!(
+            "fill=\"none\" {} stroke-width=\"{}\"{}",
+            self.paint_attributes("stroke", brush),
+            stroke_width,
+            stroke_attributes(style, stroke_width)
+        );
+        self.element(geometry, false, &attributes);
+    }
+}
+
+fn rect_attributes(rect: &RectF) -> String {
+    format!(
+        "x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+        rect.left.min(rect.right),
+        rect.top.min(rect.bottom),
+        rect.width().abs(),
+        rect.height().abs()
+    )
+}
+
+fn stroke_attributes(style: &StrokeStyle, stroke_width: f32) -> String {
+    let mut attributes = format!(
+        " stroke-linecap=\"{}\" stroke-linejoin=\"{}\" stroke-miterlimit=\"{}\"",
+        line_cap(style.get_start_cap()),
+        line_join(style.get_line_join()),
+        style.get_miter_limit()
+    );
+    let dashes = style.dash_pattern();
+    if !dashes.is_empty() {
+        let dashes: Vec<String> = dashes.iter().map(|dash| (dash * stroke_width).to_string()).collect();
+        let _ = write!(
+            attributes,
+            " stroke-dasharray=\"{}\" stroke-dashoffset=\"{}\"",
+            dashes.join(" "),
+            style.get_dash_offset() * stroke_width
+        );
+    }
+    attributes
+}
+
+/// SVG has a single cap for the whole stroke; triangle caps have no equivalent.
+fn line_cap(cap: CapStyle) -> &'static str {
+    match cap {
+        CapStyle::Flat | CapStyle::Triangle => "butt",
+        CapStyle::Square => "square",
+        CapStyle::Round => "round",
+    }
+}
+
+fn line_join(join: LineJoin) -> &'static str {
+    match join {
+        LineJoin::Miter | LineJoin::MiterOrBevel => "miter",
+        LineJoin::Bevel => "bevel",
+        LineJoin::Round => "round",
+    }
+}
+
+fn hex(color: ColorF) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", channel(color.r), channel(color.g), channel(color.b))
+}
+
+/// Path data for the figures of `path`; hollow figures are left out when filling.
+fn path_data(path: &Path, filled_only: bool) -> String {
+    let mut data = String::new();
+    for figure in path.figures() {
+        if filled_only && !figure.is_filled() {
+            continue;
+        }
+        let _ = write!(data, "M{},{}", figure.start.x, figure.start.y);
+        for segment in &figure.segments {
+            let _ = match *segment {
+                Segment::Line(p) => write!(data, " L{},{}", p.x, p.y),
+                Segment::Bezier(ref b) => write!(
+                    data,
+                    " C{},{} {},{} {},{}",
+                    b.point1.x, b.point1.y, b.point2.x, b.point2.y, b.point3.x, b.point3.y
+                ),
+                Segment::QuadBezier(ref b) => write!(data, " Q{},{} {},{}", b.point1.x, b.point1.y, b.point2.x, b.point2.y),
+                Segment::Arc(ref arc) => write!(
+                    data,
+                    " A{},{} {} {} {} {},{}",
+                    arc.size.width,
+                    arc.size.height,
+                    arc.rotation_angle,
+                    if arc.arc_size == ArcSize::Large { 1 } else { 0 },
+                    if arc.sweep_direction == SweepDirection::Clockwise { 1 } else { 0 },
+                    arc.point.x,
+                    arc.point.y
+                ),
+            };
+        }
+        data.push_str(if figure.is_closed() { " Z " } else { " " });
+    }
+    data.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use brush::{RadialGradientBrush, SolidColorBrush};
+    use geometry::Rectangle;
+
+    fn black() -> SolidColorBrush {
+        SolidColorBrush::new(ColorF::uint_rgb(0x000000, 1.0), 1.0)
+    }
+
+    #[test]
+    fn paths_carry_arcs_and_fill_rule() {
+        let mut path = Path::new();
+        path.open()
+            .fill_mode(FillMode::Winding)
+            .begin_figure((0.0, 80.0), FigureBegin::Filled, FigureEnd::Closed)
+            .add_arc(&ArcSegment::new((80.0, 80.0), (40.0, 20.0), 30.0, SweepDirection::Clockwise, ArcSize::Large))
+            .add_arc(&ArcSegment::new((0.0, 80.0), (40.0, 20.0), 0.0, SweepDirection::CounterClockwise, ArcSize::Small))
+            .end();
+        let mut canvas = SvgCanvas::new(SizeF::new(100.0, 100.0));
+        canvas.fill_geometry(&path, &black());
+        canvas.fill_geometry(&Rectangle::create(&RectF::new(0.0, 0.0, 10.0, 10.0)), &black());
+        let document = canvas.document();
+        assert!(document.contains("<path d=\"M0,80 A40,20 30 1 1 80,80 A40,20 0 0 0 0,80 Z\" fill=\"#000000\" fill-opacity=\"1\" fill-rule=\"nonzero\"/>"));
+        assert!(document.contains("<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\" fill=\"#000000\" fill-opacity=\"1\" fill-rule=\"evenodd\"/>"));
+    }
+
+    #[test]
+    fn dashes_scale_with_stroke_width() {
+        let style = StrokeStyle::create().with_dash_style(DashStyle::Dash).with_dash_offset(1.0).with_line_join(LineJoin::Round).build();
+        let mut canvas = SvgCanvas::new(SizeF::new(100.0, 100.0));
+        canvas.draw_line((0.0, 0.0).into(), (100.0, 0.0).into(), &black(), 3.0, Some(&style));
+        let document = canvas.document();
+        assert!(document.contains("<path d=\"M0,0 L100,0\" fill=\"none\" stroke=\"#000000\" stroke-opacity=\"1\" stroke-width=\"3\""));
+        assert!(document.contains(" stroke-linejoin=\"round\" "));
+        assert!(document.contains(" stroke-dasharray=\"6 6\" stroke-dashoffset=\"3\"/>"));
+
+        canvas.draw_line((0.0, 0.0).into(), (100.0, 0.0).into(), &black(), 3.0, None);
+        assert_eq!(canvas.document().matches("stroke-dasharray").count(), 1);
+    }
+
+    #[test]
+    fn radial_gradients_are_scaled_circles() {
+        let mut canvas = SvgCanvas::new(SizeF::new(100.0, 100.0));
+        let brush = RadialGradientBrush::create(&canvas)
+            .with_center((50.0, 50.0).into())
+            .with_origin_offset((10.0, 10.0).into())
+            .with_radius(40.0, 20.0)
+            .with_stop((0.0, ColorF::uint_rgb(0xFF0000, 1.0)))
+            .with_stop((1.0, ColorF::uint_rgb(0x0000FF, 0.5)))
+            .build();
+        canvas.fill_rectangle(RectF::new(0.0, 0.0, 100.0, 100.0), &brush);
+        let document = canvas.document();
+        assert!(document.contains(
+            "<radialGradient id=\"gradient1\" gradientUnits=\"userSpaceOnUse\" spreadMethod=\"pad\" \
+             cx=\"50\" cy=\"50\" r=\"40\" fx=\"60\" fy=\"70\" \
+             gradientTransform=\"translate(50 50) scale(1 0.5) translate(-50 -50)\">"
+        ));
+        assert!(document.contains("<stop offset=\"1\" stop-color=\"#0000ff\" stop-opacity=\"0.5\"/>"));
+        assert!(document.contains("fill=\"url(#gradient1)\" fill-opacity=\"1\""));
+    }
+
+    #[test]
+    fn negative_radii_draw_the_same_ellipse() {
+        let mut canvas = SvgCanvas::new(SizeF::new(100.0, 100.0));
+        canvas.fill_ellipse(::math::Ellipse::new((50.0, 50.0), -10.0, -5.0), &black());
+        assert!(canvas.document().contains("<ellipse cx=\"50\" cy=\"50\" rx=\"10\" ry=\"5\" "));
+    }
+
+    #[test]
+    fn clear_discards_earlier_elements() {
+        let mut canvas = SvgCanvas::new(SizeF::new(100.0, 100.0));
+        canvas.fill_rectangle(RectF::new(0.0, 0.0, 10.0, 10.0), &black());
+        canvas.clear(ColorF::uint_rgb(0xFFFFFF, 1.0));
+        canvas.fill_ellipse(::math::Ellipse::new((50.0, 50.0), 10.0, 5.0), &black());
+        assert_eq!(
+            canvas.document(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"100\" viewBox=\"0 0 100 100\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\" fill-opacity=\"1\"/>\n\
+             <ellipse cx=\"50\" cy=\"50\" rx=\"10\" ry=\"5\" fill=\"#000000\" fill-opacity=\"1\" fill-rule=\"evenodd\"/>\n\
+             </svg>\n"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FigureBegin {
+    Filled,
+    Hollow,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FigureEnd {
+    Open,
+    Closed,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FillMode {
+    #[default]
+    Alternate,
+    Winding,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SweepDirection {
+    CounterClockwise,
+    Clockwise,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArcSize {
+    Small,
+    Large,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CapStyle {
+    Flat,
+    Square,
+    Round,
+    Triangle,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineJoin {
+    Miter,
+    Bevel,
+    Round,
+    MiterOrBevel,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DashStyle {
+    Solid,
+    Dash,
+    Dot,
+    DashDot,
+    DashDotDot,
+    Custom,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtendMode {
+    Clamp,
+    Wrap,
+    Mirror,
+}
+
+/// How the fill of a geometry relates to the fill of another one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GeometryRelation {
+    Disjoint,
+    /// The geometry lies entirely within the other one.
+    IsContained,
+    /// The other geometry lies entirely within this one.
+    Contains,
+    Overlap,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CombineMode {
+    Union,
+    Intersect,
+    Xor,
+    Exclude,
+}
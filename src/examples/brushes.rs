@@ -1,28 +1,45 @@
-use direct2d::brush::Brush;
-use direct2d::brush::LinearGradientBrush;
-use direct2d::brush::SolidColorBrush;
-use direct2d::math::Point2F;
-use direct2d::render_target::HwndRenderTarget;
-use direct2d::RenderTarget;
+use brush::Brush;
+use brush::LinearGradientBrush;
+use brush::RadialGradientBrush;
+use brush::SolidColorBrush;
+use canvas::Canvas;
+use enums::*;
+use math::*;
 use examples::Example;
-use direct2d::brush::RadialGradientBrush;
-use direct2d::enums::*;
-use gui::State;
-use direct2d::stroke_style::StrokeStyle;
-use direct2d::Factory;
+use state::State;
+use stroke_style::StrokeStyle;
 use examples::COLOR_BLUE;
 use examples::COLOR_BLACK;
 use examples::COLOR_WHITE;
 use examples::COLOR_YELLOW;
 
+/// The scenes `Brushes` can draw, one at a time, in the order of `SCENE_NAMES`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum Scene {
+    #[default]
+    SolidBrush,
+    LinearGradBrush,
+    RadialGradBrush,
+    BrushStrokes,
+}
+
+const SCENES: [Scene; 4] = [
+    Scene::SolidBrush,
+    Scene::LinearGradBrush,
+    Scene::RadialGradBrush,
+    Scene::BrushStrokes,
+];
+
+const SCENE_NAMES: [&str; 4] = ["solid", "linear", "radial", "strokes"];
+
 #[derive(Default)]
 pub struct Brushes {
+    scene: Scene,
     solid_brush: Option<SolidBrush>,
     linear_gradient_brush: Option<LinearGradBrush>,
     radial_gradient_brush: Option<RadialGradBrush>,
     style_strokes_brush: Option<BrushStrokes>,
-    stroke_style: Option<StrokeStyle>
-
+    stroke_style: Option<StrokeStyle>,
 }
 
 impl Example for Brushes {
@@ -30,28 +47,41 @@ impl Example for Brushes {
         Brushes::default()
     }
 
-    fn create_device_resources(&mut self, render_target: &HwndRenderTarget) {
-        self.solid_brush = Some(SolidBrush::new(render_target));
-        self.linear_gradient_brush = Some(LinearGradBrush::new(render_target));
-        self.radial_gradient_brush = Some(RadialGradBrush::new(render_target));
-        self.style_strokes_brush = Some(BrushStrokes::new(render_target, self.stroke_style.take().unwrap()));
+    fn create_device_resources(&mut self, canvas: &dyn Canvas) {
+        self.solid_brush = Some(SolidBrush::new(canvas));
+        self.linear_gradient_brush = Some(LinearGradBrush::new(canvas));
+        self.radial_gradient_brush = Some(RadialGradBrush::new(canvas));
+        self.style_strokes_brush = Some(BrushStrokes::new(canvas, self.stroke_style.clone().unwrap()));
     }
 
-    fn create_device_independent_resources(&mut self, factory: &Factory) {
-        let style = StrokeStyle::create(factory)
+    fn create_device_independent_resources(&mut self) {
+        let style = StrokeStyle::create()
             .with_line_join(LineJoin::Round)
             .with_dash_cap(CapStyle::Round)
             .with_dash_style(DashStyle::DashDot)
-            .build().unwrap();
+            .build();
         self.stroke_style = Some(style);
     }
 
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-        //Choose one
-//        self.solid_brush.as_mut().unwrap().draw(_state, render_target);
-//        self.linear_gradient_brush.as_mut().unwrap().draw(_state, render_target);
-//        self.radial_gradient_brush.as_mut().unwrap().draw(_state, render_target);
-        self.style_strokes_brush.as_mut().unwrap().draw(_state, render_target);
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas) {
+        match self.scene {
+            Scene::SolidBrush => self.solid_brush.as_mut().unwrap().draw(state, canvas),
+            Scene::LinearGradBrush => self.linear_gradient_brush.as_mut().unwrap().draw(state, canvas),
+            Scene::RadialGradBrush => self.radial_gradient_brush.as_mut().unwrap().draw(state, canvas),
+            Scene::BrushStrokes => self.style_strokes_brush.as_mut().unwrap().draw(state, canvas),
+        }
+    }
+
+    fn scenes(&self) -> &'static [&'static str] {
+        &SCENE_NAMES
+    }
+
+    fn scene(&self) -> usize {
+        self.scene as usize
+    }
+
+    fn set_scene(&mut self, scene: usize) {
+        self.scene = SCENES[scene];
     }
 }
 
@@ -60,30 +90,30 @@ struct SolidBrush {
 }
 
 impl SolidBrush {
-    fn new(render_target: &HwndRenderTarget) -> Self {
-        let inner = SolidColorBrush::create(render_target).with_color(COLOR_BLUE).build().unwrap();
+    fn new(canvas: &dyn Canvas) -> Self {
+        let inner = SolidColorBrush::create(canvas).with_color(COLOR_BLUE).build();
         SolidBrush {
             inner
         }
     }
 
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-        render_target.clear(COLOR_BLUE);
+    fn draw(&mut self, _state: &State, canvas: &mut dyn Canvas) {
+        canvas.clear(COLOR_BLUE.into());
 
         self.inner.set_opacity(1.0);
-        let size = render_target.get_size();
+        let size = canvas.get_size();
         self.inner.set_color(&COLOR_BLACK.into());
-        let rect = (100.0, 100.0, size.width - 100.0, 200.0);
-        render_target.fill_rectangle(rect, &self.inner);
+        let rect = RectF::new(100.0, 100.0, size.width - 100.0, 200.0);
+        canvas.fill_rectangle(rect, &self.inner);
 
         self.inner.set_color(&COLOR_WHITE.into());
-        let rect = (100.0, 300.0, size.width - 100.0, 400.0);
-        render_target.fill_rectangle(rect, &self.inner);
+        let rect = RectF::new(100.0, 300.0, size.width - 100.0, 400.0);
+        canvas.fill_rectangle(rect, &self.inner);
 
         self.inner.set_opacity(0.5);
         self.inner.set_color(&COLOR_YELLOW.into());
-        let rect = (150.0, 150.0, size.width - 150.0, 350.0);
-        render_target.fill_rectangle(rect, &self.inner);
+        let rect = RectF::new(150.0, 150.0, size.width - 150.0, 350.0);
+        canvas.fill_rectangle(rect, &self.inner);
     }
 }
 
@@ -92,58 +122,56 @@ struct LinearGradBrush {
 }
 
 impl LinearGradBrush {
-    fn new(render_target: &HwndRenderTarget) -> Self {
+    fn new(canvas: &dyn Canvas) -> Self {
         let stops = [
             (0.0, COLOR_WHITE.into()).into(),
             (1.0, COLOR_BLUE.into()).into()];
-        let inner = LinearGradientBrush::create(render_target).with_extend_mode(ExtendMode ::Mirror).with_stops(&stops).build().unwrap();
+        let inner = LinearGradientBrush::create(canvas).with_extend_mode(ExtendMode::Mirror).with_stops(&stops).build();
         LinearGradBrush {
             inner
         }
     }
 
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-        let size = render_target.get_size();
+    fn draw(&mut self, _state: &State, canvas: &mut dyn Canvas) {
+        let size = canvas.get_size();
 
         self.inner.set_end_point(Point2F::new(size.width / 4.0, size.height / 4.0));
-        let rect = (0.0, 0.0, size.width, size.height);
-        render_target.fill_rectangle(rect, &self.inner);
+        let rect = RectF::new(0.0, 0.0, size.width, size.height);
+        canvas.fill_rectangle(rect, &self.inner);
     }
 }
 
-
-
 struct RadialGradBrush {
     inner: RadialGradientBrush,
     mouse_pos: (i32, i32),
 }
 
 impl RadialGradBrush {
-    fn new(render_target: &HwndRenderTarget) -> Self {
+    fn new(canvas: &dyn Canvas) -> Self {
         let stops = [
             (0.0, COLOR_WHITE.into()).into(),
             (1.0, COLOR_BLUE.into()).into()];
-        let inner = RadialGradientBrush::create(render_target).with_stops(&stops).build().unwrap();
+        let inner = RadialGradientBrush::create(canvas).with_stops(&stops).build();
         RadialGradBrush {
             inner,
             mouse_pos: (0, 0),
         }
     }
 
-    fn draw(&mut self, state: &State, render_target: &mut HwndRenderTarget) {
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas) {
         if state.mouse_pos != self.mouse_pos {
             self.mouse_pos = state.mouse_pos;
             let center = self.inner.get_center();
             self.inner.set_gradient_origin_offset(Point2F::new(self.mouse_pos.0 as f32 - center.x, self.mouse_pos.1 as f32 - center.y));
         }
 
-        let size = render_target.get_size();
+        let size = canvas.get_size();
 
         self.inner.set_center(Point2F::new(size.width / 2.0, size.height / 2.0));
         self.inner.set_radius_x(size.width / 2.0);
         self.inner.set_radius_y(size.height / 2.0);
-        let rect = (0.0, 0.0, size.width, size.height);
-        render_target.fill_rectangle(rect, &self.inner);
+        let rect = RectF::new(0.0, 0.0, size.width, size.height);
+        canvas.fill_rectangle(rect, &self.inner);
     }
 }
 
@@ -153,19 +181,19 @@ struct BrushStrokes {
 }
 
 impl BrushStrokes {
-    fn new(render_target: &HwndRenderTarget, style: StrokeStyle) -> Self {
-        let inner = SolidColorBrush::create(render_target).with_color(COLOR_BLUE).build().unwrap();
+    fn new(canvas: &dyn Canvas, style: StrokeStyle) -> Self {
+        let inner = SolidColorBrush::create(canvas).with_color(COLOR_BLUE).build();
         BrushStrokes {
             inner,
             style,
         }
     }
 
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-        render_target.clear(COLOR_WHITE);
-        let size = render_target.get_size();
+    fn draw(&mut self, _state: &State, canvas: &mut dyn Canvas) {
+        canvas.clear(COLOR_WHITE.into());
+        let size = canvas.get_size();
 
-        let rect = (100.0, 100.0, size.width - 100.0, size.height - 100.0);
-        render_target.draw_rectangle(rect, &self.inner, 20.0, Some(&self.style));
+        let rect = RectF::new(100.0, 100.0, size.width - 100.0, size.height - 100.0);
+        canvas.draw_rectangle(rect, &self.inner, 20.0, Some(&self.style));
     }
-}
\ No newline at end of file
+}
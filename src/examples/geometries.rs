@@ -1,28 +1,53 @@
-use direct2d::brush::Brush;
-use direct2d::brush::SolidColorBrush;
-use direct2d::enums::*;
-use direct2d::Factory;
-use direct2d::geometry::Ellipse as EllipseGeom;
-use direct2d::geometry::Path;
-use direct2d::geometry::Rectangle;
-use direct2d::geometry::RoundedRectangle;
-use direct2d::math::*;
-use direct2d::math::RectF;
-use direct2d::render_target::HwndRenderTarget;
-use direct2d::RenderTarget;
+use brush::Brush;
+use brush::SolidColorBrush;
+use canvas::Canvas;
+use enums::*;
+use geometry::flatten::DEFAULT_FLATTENING_TOLERANCE;
+use geometry::Ellipse as EllipseGeom;
+use geometry::Geometry;
+use geometry::GeometryGroup;
+use geometry::Path;
+use geometry::Rectangle;
+use geometry::RoundedRectangle;
+use geometry::TransformedGeometry;
+use math::*;
 use examples::COLOR_BLACK;
 use examples::COLOR_BLUE;
 use examples::COLOR_WHITE;
 use examples::COLOR_YELLOW;
 use examples::Example;
-use gui::State;
-use winapi::um::d2d1::D2D1_ROUNDED_RECT;
-use direct2d::geometry::path::GeometryBuilder;
+use state::State;
 use examples::COLOR_RED;
 use examples::COLOR_GREEN;
 
+/// The scenes `Geometries` can draw, one at a time, in the order of `SCENE_NAMES`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum Scene {
+    #[default]
+    Shapes,
+    SimpleGeometries,
+    PathGeometries,
+    ArcGeometries,
+    BezierGeometries,
+    CombinedGeometries,
+    GroupedGeometries,
+}
+
+const SCENES: [Scene; 7] = [
+    Scene::Shapes,
+    Scene::SimpleGeometries,
+    Scene::PathGeometries,
+    Scene::ArcGeometries,
+    Scene::BezierGeometries,
+    Scene::CombinedGeometries,
+    Scene::GroupedGeometries,
+];
+
+const SCENE_NAMES: [&str; 7] = ["shapes", "simple", "path", "arc", "bezier", "combine", "group"];
+
 #[derive(Default)]
 pub struct Geometries {
+    scene: Scene,
     shapes: Option<Shapes>,
     simple_geometries: Option<SimpleGeometries>,
     simple_geometries_resources: Option<SimpleGeometriesResources>,
@@ -32,6 +57,10 @@ pub struct Geometries {
     arc_geometries_resources: Option<ArcGeometriesResources>,
     bezier_geometries: Option<BezierGeometries>,
     bezier_geometries_resources: Option<BezierGeometriesResources>,
+    combined_geometries: Option<CombinedGeometries>,
+    combined_geometries_resources: Option<CombinedGeometriesResources>,
+    grouped_geometries: Option<GroupedGeometries>,
+    grouped_geometries_resources: Option<GroupedGeometriesResources>,
 }
 
 impl Example for Geometries {
@@ -39,27 +68,141 @@ impl Example for Geometries {
         Default::default()
     }
 
-    fn create_device_resources(&mut self, render_target: &HwndRenderTarget) {
-        self.shapes = Some(Shapes::new(render_target));
-        self.simple_geometries = Some(SimpleGeometries::new(render_target, self.simple_geometries_resources.take().unwrap()));
-        self.path_geometries = Some(PathGeometries::new(render_target, self.path_geometries_resources.take().unwrap()));
-        self.arc_geometries = Some(ArcGeometries::new(render_target, self.arc_geometries_resources.take().unwrap()));
-        self.bezier_geometries = Some(BezierGeometries::new(render_target, self.bezier_geometries_resources.take().unwrap()));
+    fn create_device_resources(&mut self, canvas: &dyn Canvas) {
+        self.shapes = Some(Shapes::new(canvas));
+        self.simple_geometries = Some(SimpleGeometries::new(canvas, self.simple_geometries_resources.clone().unwrap()));
+        self.path_geometries = Some(PathGeometries::new(canvas, self.path_geometries_resources.clone().unwrap()));
+        self.arc_geometries = Some(ArcGeometries::new(canvas, self.arc_geometries_resources.clone().unwrap()));
+        self.bezier_geometries = Some(BezierGeometries::new(canvas, self.bezier_geometries_resources.clone().unwrap()));
+        self.combined_geometries = Some(CombinedGeometries::new(canvas, self.combined_geometries_resources.clone().unwrap()));
+        self.grouped_geometries = Some(GroupedGeometries::new(canvas, self.grouped_geometries_resources.clone().unwrap()));
+    }
+
+    fn create_device_independent_resources(&mut self) {
+        self.simple_geometries_resources = Some(SimpleGeometries::create_device_independent_resources());
+        self.path_geometries_resources = Some(PathGeometries::create_device_independent_resources());
+        self.arc_geometries_resources = Some(ArcGeometries::create_device_independent_resources());
+        self.bezier_geometries_resources = Some(BezierGeometries::create_device_independent_resources());
+        self.combined_geometries_resources = Some(CombinedGeometries::create_device_independent_resources());
+        self.grouped_geometries_resources = Some(GroupedGeometries::create_device_independent_resources());
+    }
+
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas) {
+        match self.scene {
+            Scene::Shapes => self.shapes.as_mut().unwrap().draw(state, canvas),
+            Scene::SimpleGeometries => self.simple_geometries.as_mut().unwrap().draw(state, canvas),
+            Scene::PathGeometries => self.path_geometries.as_mut().unwrap().draw(state, canvas),
+            Scene::ArcGeometries => self.arc_geometries.as_mut().unwrap().draw(state, canvas),
+            Scene::BezierGeometries => self.bezier_geometries.as_mut().unwrap().draw(state, canvas),
+            Scene::CombinedGeometries => self.combined_geometries.as_mut().unwrap().draw(state, canvas),
+            Scene::GroupedGeometries => self.grouped_geometries.as_mut().unwrap().draw(state, canvas),
+        }
+    }
+
+    fn scenes(&self) -> &'static [&'static str] {
+        &SCENE_NAMES
+    }
+
+    fn scene(&self) -> usize {
+        self.scene as usize
+    }
+
+    fn set_scene(&mut self, scene: usize) {
+        self.scene = SCENES[scene];
+    }
+}
+
+struct CombinedGeometries {
+    brush: SolidColorBrush,
+    outline_brush: SolidColorBrush,
+    combined: [Path; 4],
+}
+
+type CombinedGeometriesResources = [Path; 4];
+
+impl CombinedGeometries {
+    /// The rectangle and ellipse of `SimpleGeometries` combined in every mode, laid out
+    /// in a grid with one mode per quarter of the window.
+    fn create_device_independent_resources() -> CombinedGeometriesResources {
+        let (rect, _, ellipse) = SimpleGeometries::create_device_independent_resources();
+        let combine = |mode, column: f32, row: f32| {
+            let path = rect.combine_with_geometry(&ellipse, mode, None, DEFAULT_FLATTENING_TOLERANCE);
+            let place = Matrix3x2F::scale((0.8, 0.8), (0.0, 0.0)) * Matrix3x2F::translation((column * 512.0 + 6.0, row * 384.0 + 12.0));
+            path.transformed(&place)
+        };
+        [
+            combine(CombineMode::Union, 0.0, 0.0),
+            combine(CombineMode::Intersect, 1.0, 0.0),
+            combine(CombineMode::Xor, 0.0, 1.0),
+            combine(CombineMode::Exclude, 1.0, 1.0),
+        ]
     }
 
-    fn create_device_independent_resources(&mut self, factory: &Factory) {
-        self.simple_geometries_resources = Some(SimpleGeometries::create_device_independent_resources(factory));
-        self.path_geometries_resources = Some(PathGeometries::create_device_independent_resources(factory));
-        self.arc_geometries_resources = Some(ArcGeometries::create_device_independent_resources(factory));
-        self.bezier_geometries_resources = Some(BezierGeometries::create_device_independent_resources(factory));
+    fn new(canvas: &dyn Canvas, resources: CombinedGeometriesResources) -> Self {
+        let brush = SolidColorBrush::create(canvas).with_color(COLOR_WHITE).build();
+        let outline_brush = SolidColorBrush::create(canvas).with_color(COLOR_BLACK).build();
+        CombinedGeometries {
+            brush,
+            outline_brush,
+            combined: resources,
+        }
     }
 
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-//        self.shapes.as_mut().unwrap().draw(_state, render_target);
-//        self.simple_geometries.as_mut().unwrap().draw(_state, render_target);
-//        self.path_geometries.as_mut().unwrap().draw(_state, render_target);
-//        self.arc_geometries.as_mut().unwrap().draw(_state, render_target);
-        self.bezier_geometries.as_mut().unwrap().draw(_state, render_target);
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas) {
+        canvas.clear(COLOR_BLUE.into());
+        let mouse = state.mouse_point();
+        for path in &self.combined {
+            let hovered = path.fill_contains_point(mouse, None, DEFAULT_FLATTENING_TOLERANCE);
+            self.brush.set_color(&(if hovered { COLOR_YELLOW } else { COLOR_WHITE }).into());
+            canvas.fill_geometry(path, &self.brush);
+            canvas.draw_geometry(path, &self.outline_brush, 3.0, None);
+        }
+    }
+}
+
+struct GroupedGeometries {
+    brush: SolidColorBrush,
+    outline_brush: SolidColorBrush,
+    copies: [TransformedGeometry; 4],
+}
+
+type GroupedGeometriesResources = [TransformedGeometry; 4];
+
+impl GroupedGeometries {
+    /// The shapes of `SimpleGeometries` as a single group, filled with the alternate
+    /// rule, and drawn turned a little further in each quarter of the window.
+    fn create_device_independent_resources() -> GroupedGeometriesResources {
+        let (rect, rounded_rect, ellipse) = SimpleGeometries::create_device_independent_resources();
+        let group = GeometryGroup::create(FillMode::Alternate, &[&rect, &rounded_rect, &ellipse]);
+        let place = |degrees: f32, column: f32, row: f32| {
+            // Turned and scaled around the middle of the group, which then goes to the
+            // middle of its quarter.
+            let (turn, scale) = (Matrix3x2F::rotation(degrees.to_radians(), (325.0, 225.0)), Matrix3x2F::scale((0.65, 0.65), (325.0, 225.0)));
+            let place = Matrix3x2F::translation((column * 512.0 + 256.0 - 325.0, row * 384.0 + 192.0 - 225.0));
+            group.transformed(&(turn * scale * place))
+        };
+        [place(0.0, 0.0, 0.0), place(10.0, 1.0, 0.0), place(20.0, 0.0, 1.0), place(30.0, 1.0, 1.0)]
+    }
+
+    fn new(canvas: &dyn Canvas, resources: GroupedGeometriesResources) -> Self {
+        let brush = SolidColorBrush::create(canvas).with_color(COLOR_WHITE).build();
+        let outline_brush = SolidColorBrush::create(canvas).with_color(COLOR_BLACK).build();
+        GroupedGeometries {
+            brush,
+            outline_brush,
+            copies: resources,
+        }
+    }
+
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas) {
+        canvas.clear(COLOR_BLUE.into());
+        let mouse = state.mouse_point();
+        for copy in &self.copies {
+            let hovered = copy.fill_contains_point(mouse, None, DEFAULT_FLATTENING_TOLERANCE);
+            self.brush.set_color(&(if hovered { COLOR_YELLOW } else { COLOR_WHITE }).into());
+            canvas.fill_geometry(copy, &self.brush);
+            canvas.draw_geometry(copy, &self.outline_brush, 3.0, None);
+        }
     }
 }
 
@@ -74,26 +217,17 @@ struct BezierGeometries {
 type BezierGeometriesResources = (Path, Path, Point2F, Point2F);
 
 impl BezierGeometries {
-    fn create_device_independent_resources(factory: &Factory) -> (BezierGeometriesResources) {
-        let begin = (100.0, 600.0);
-        let end = (900.0, 600.0).into();
+    fn create_device_independent_resources() -> BezierGeometriesResources {
         let p1 = (50.0, 50.0).into();
         let p2 = (600.0, 50.0).into();
-        let mut path1 = Path::create(factory).unwrap();
-        {
-            let segment = BezierSegment::new(p1, p2, end);
-            path1.open().unwrap().begin_figure(begin, FigureBegin::Filled, FigureEnd::Closed).add_bezier(&segment).end();
-        }
-        let mut path2 = Path::create(factory).unwrap();
-        {
-            let segments = [QuadBezierSegment::new((400.0, 0.0).into(), (400.0, 300.0).into()), QuadBezierSegment::new((400.0, 600.0).into(), end)];
-            path2.open().unwrap().begin_figure(begin, FigureBegin::Filled, FigureEnd::Closed).add_quadratic_beziers(&segments).end();
-        }
+        let path1 = Path::from_svg("M100 600 C50 50 600 50 900 600 Z").unwrap();
+        // The second curve's control point mirrors the first one's.
+        let path2 = Path::from_svg("M100 600 Q400 0 400 300 T900 600 Z").unwrap();
         (path1, path2, p1, p2)
     }
 
-    fn new(render_target: &HwndRenderTarget, resources: BezierGeometriesResources) -> Self {
-        let brush = SolidColorBrush::create(render_target).with_color(COLOR_BLUE).build().unwrap();
+    fn new(canvas: &dyn Canvas, resources: BezierGeometriesResources) -> Self {
+        let brush = SolidColorBrush::create(canvas).with_color(COLOR_BLUE).build();
         BezierGeometries {
             brush,
             path1: resources.0,
@@ -103,18 +237,35 @@ impl BezierGeometries {
         }
     }
 
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-        render_target.clear(COLOR_BLUE);
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas) {
+        canvas.clear(COLOR_BLUE.into());
 
         self.brush.set_color(&COLOR_WHITE.into());
         let ellipse = Ellipse::new(self.p1, 10.0, 10.0);
-        render_target.fill_ellipse(ellipse, &self.brush);
+        canvas.fill_ellipse(ellipse, &self.brush);
         let ellipse = Ellipse::new(self.p2, 10.0, 10.0);
-        render_target.fill_ellipse(ellipse, &self.brush);
-
-        render_target.draw_geometry(&self.path1, &self.brush, 10.0, None);
-        self.brush.set_color(&COLOR_RED.into());
-        render_target.draw_geometry(&self.path2, &self.brush, 10.0, None);
+        canvas.fill_ellipse(ellipse, &self.brush);
+
+        let mouse = state.mouse_point();
+        let hovered = |path: &Path| path.stroke_contains_point(mouse, 10.0, None, None, DEFAULT_FLATTENING_TOLERANCE);
+        let (color1, color2) = match (hovered(&self.path1), hovered(&self.path2)) {
+            (_, true) => (COLOR_WHITE, COLOR_YELLOW),
+            (true, false) => (COLOR_YELLOW, COLOR_RED),
+            (false, false) => (COLOR_WHITE, COLOR_RED),
+        };
+        self.brush.set_color(&color1.into());
+        canvas.draw_geometry(&self.path1, &self.brush, 10.0, None);
+        self.brush.set_color(&color2.into());
+        canvas.draw_geometry(&self.path2, &self.brush, 10.0, None);
+
+        // A marker on each curve follows the mouse across the window.
+        let progress = (mouse.x / canvas.get_size().width).clamp(0.0, 1.0);
+        self.brush.set_color(&COLOR_BLACK.into());
+        for path in &[&self.path1, &self.path2] {
+            let length = path.compute_length(None, DEFAULT_FLATTENING_TOLERANCE);
+            let (point, _) = path.point_at_length(length * progress, None, DEFAULT_FLATTENING_TOLERANCE);
+            canvas.fill_ellipse(Ellipse::new(point, 12.0, 12.0), &self.brush);
+        }
     }
 }
 
@@ -131,28 +282,28 @@ struct ArcGeometries {
 type ArcGeometriesResources = (Path, Path, Path, Path, Point2F, Point2F);
 
 impl ArcGeometries {
-    fn create_device_independent_resources(factory: &Factory) -> (ArcGeometriesResources) {
+    fn create_device_independent_resources() -> ArcGeometriesResources {
         let begin = (400.0, 200.0).into();
         let end = (600.0, 500.0).into();
-        let path1 = ArcGeometries::build_path(factory, begin, end, SweepDirection::CounterClockwise, ArcSize::Large);
-        let path2 = ArcGeometries::build_path(factory, begin, end, SweepDirection::Clockwise, ArcSize::Large);
-        let path3 = ArcGeometries::build_path(factory, begin, end, SweepDirection::CounterClockwise, ArcSize::Small);
-        let path4 = ArcGeometries::build_path(factory, begin, end, SweepDirection::Clockwise, ArcSize::Small);
+        let path1 = ArcGeometries::build_path(begin, end, SweepDirection::CounterClockwise, ArcSize::Large);
+        let path2 = ArcGeometries::build_path(begin, end, SweepDirection::Clockwise, ArcSize::Large);
+        let path3 = ArcGeometries::build_path(begin, end, SweepDirection::CounterClockwise, ArcSize::Small);
+        let path4 = ArcGeometries::build_path(begin, end, SweepDirection::Clockwise, ArcSize::Small);
         (path1, path2, path3, path4, begin, end)
     }
 
-    fn build_path(factory: &Factory, begin: Point2F, end: Point2F, direction: SweepDirection, size: ArcSize) -> Path {
-        let mut path = Path::create(&factory).unwrap();
+    fn build_path(begin: Point2F, end: Point2F, direction: SweepDirection, size: ArcSize) -> Path {
+        let mut path = Path::new();
         {
-            let builder = path.open().unwrap();
-            let arc = ArcSegment::new(end, (200.0, 200.0).into(), 0.0, direction, size);
+            let builder = path.open();
+            let arc = ArcSegment::new(end, (200.0, 200.0), 0.0, direction, size);
             builder.begin_figure(begin, FigureBegin::Filled, FigureEnd::Open).add_arc(&arc).end();
         }
         path
     }
 
-    fn new(render_target: &HwndRenderTarget, resources: ArcGeometriesResources) -> Self {
-        let brush = SolidColorBrush::create(render_target).with_color(COLOR_BLUE).build().unwrap();
+    fn new(canvas: &dyn Canvas, resources: ArcGeometriesResources) -> Self {
+        let brush = SolidColorBrush::create(canvas).with_color(COLOR_BLUE).build();
         ArcGeometries {
             brush,
             arc1: resources.0,
@@ -164,37 +315,63 @@ impl ArcGeometries {
         }
     }
 
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-        render_target.clear(COLOR_WHITE);
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas) {
+        canvas.clear(COLOR_WHITE.into());
         self.brush.set_color(&COLOR_YELLOW.into());
         let ellipse = Ellipse::new(self.begin, 50.0, 50.0);
-        render_target.fill_ellipse(ellipse, &self.brush);
+        canvas.fill_ellipse(ellipse, &self.brush);
 
         self.brush.set_color(&COLOR_BLUE.into());
         let ellipse = Ellipse::new(self.end, 50.0, 50.0);
-        render_target.fill_ellipse(ellipse, &self.brush);
-        self.brush.set_color(&COLOR_RED.into());
-        render_target.draw_geometry(&self.arc1, &self.brush, 10.0, None);
-        render_target.draw_geometry(&self.arc2, &self.brush, 10.0, None);
-
-        self.brush.set_color(&COLOR_GREEN.into());
-        render_target.draw_geometry(&self.arc3, &self.brush, 10.0, None);
-        render_target.draw_geometry(&self.arc4, &self.brush, 10.0, None);
+        canvas.fill_ellipse(ellipse, &self.brush);
+
+        // The arc under the mouse is drawn last, on top of the others.
+        let mouse = state.mouse_point();
+        let arcs = [(&self.arc1, COLOR_RED), (&self.arc2, COLOR_RED), (&self.arc3, COLOR_GREEN), (&self.arc4, COLOR_GREEN)];
+        let hovered = arcs
+            .iter()
+            .rposition(|&(arc, _)| arc.stroke_contains_point(mouse, 10.0, None, None, DEFAULT_FLATTENING_TOLERANCE));
+        for (i, &(arc, color)) in arcs.iter().enumerate() {
+            if Some(i) != hovered {
+                ArcGeometries::draw_arc(canvas, &mut self.brush, arc, color);
+            }
+        }
+        if let Some(i) = hovered {
+            ArcGeometries::draw_arc(canvas, &mut self.brush, arcs[i].0, COLOR_YELLOW);
+        }
+    }
+
+    /// Draws the arc with an arrow head halfway along, pointing the way it sweeps.
+    fn draw_arc(canvas: &mut dyn Canvas, brush: &mut SolidColorBrush, arc: &Path, color: u32) {
+        brush.set_color(&color.into());
+        canvas.draw_geometry(arc, brush, 10.0, None);
+        let length = arc.compute_length(None, DEFAULT_FLATTENING_TOLERANCE);
+        let (point, tangent) = arc.point_at_length(length / 2.0, None, DEFAULT_FLATTENING_TOLERANCE);
+        let normal = Vector2F::new(-tangent.y, tangent.x);
+        let back = point - tangent * 10.0;
+        let mut arrow = Path::new();
+        arrow
+            .open()
+            .begin_figure(point + tangent * 15.0, FigureBegin::Filled, FigureEnd::Closed)
+            .add_lines(&[back + normal * 15.0, back - normal * 15.0])
+            .end();
+        canvas.fill_geometry(&arrow, brush);
     }
 }
 
 struct PathGeometries {
     brush: SolidColorBrush,
     path: Path,
+    stars: [Path; 2],
 }
 
-type PathGeometriesResources = (Path);
+type PathGeometriesResources = (Path, [Path; 2]);
 
 impl PathGeometries {
-    fn create_device_independent_resources(factory: &Factory) -> (PathGeometriesResources) {
-        let mut path = Path::create(&factory).unwrap();
+    fn create_device_independent_resources() -> PathGeometriesResources {
+        let mut path = Path::new();
         {
-            let mut builder = path.open().unwrap();
+            let mut builder = path.open().fill_mode(FillMode::Alternate);
             builder = builder.begin_figure((50.0, 50.0), FigureBegin::Filled, FigureEnd::Closed)
                 .add_line((250.0, 30.0))
                 .add_lines(&[(270.0, 100.0).into(), (200.0, 100.0).into()])
@@ -204,23 +381,52 @@ impl PathGeometries {
                 .add_lines(&[(500.0, 750.0).into(), (750.0, 750.0).into(), (750.0, 500.0).into()])
                 .end();
         }
-        (path)
+        // The same self-intersecting star with either fill mode: only the winding one
+        // fills the pentagon in the middle, which the figure winds around twice.
+        let stars = [
+            PathGeometries::star((220.0, 560.0).into(), FillMode::Alternate),
+            PathGeometries::star((650.0, 220.0).into(), FillMode::Winding),
+        ];
+        (path, stars)
     }
 
-    fn new(render_target: &HwndRenderTarget, resources: PathGeometriesResources) -> Self {
-        let brush = SolidColorBrush::create(render_target).with_opacity(0.8).with_color(COLOR_BLUE).build().unwrap();
+    fn star(center: Point2F, fill_mode: FillMode) -> Path {
+        let points: Vec<Point2F> = (0..5)
+            .map(|i| {
+                // Every other corner of a pentagon, starting from the top.
+                let angle = (i as f32 * 0.8 - 0.5) * ::std::f32::consts::PI;
+                center + Vector2F::new(angle.cos(), angle.sin()) * 120.0
+            })
+            .collect();
+        let mut star = Path::new();
+        star.open()
+            .fill_mode(fill_mode)
+            .begin_figure(points[0], FigureBegin::Filled, FigureEnd::Closed)
+            .add_lines(&points[1..])
+            .end();
+        star
+    }
+
+    fn new(canvas: &dyn Canvas, resources: PathGeometriesResources) -> Self {
+        let brush = SolidColorBrush::create(canvas).with_opacity(0.8).with_color(COLOR_BLUE).build();
         PathGeometries {
-            path: resources,
+            path: resources.0,
+            stars: resources.1,
             brush,
         }
     }
 
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-        render_target.clear(COLOR_BLUE);
-        self.brush.set_color(&COLOR_YELLOW.into());
-        render_target.fill_geometry(&self.path, &self.brush);
-        self.brush.set_color(&COLOR_BLACK.into());
-        render_target.draw_geometry(&self.path, &self.brush, 10.0, None);
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas) {
+        canvas.clear(COLOR_BLUE.into());
+        let mouse = state.mouse_point();
+        for path in ::std::iter::once(&self.path).chain(self.stars.iter()) {
+            let hovered = path.fill_contains_point(mouse, None, DEFAULT_FLATTENING_TOLERANCE)
+                || path.stroke_contains_point(mouse, 10.0, None, None, DEFAULT_FLATTENING_TOLERANCE);
+            self.brush.set_color(&if hovered { COLOR_GREEN } else { COLOR_YELLOW }.into());
+            canvas.fill_geometry(path, &self.brush);
+            self.brush.set_color(&COLOR_BLACK.into());
+            canvas.draw_geometry(path, &self.brush, 10.0, None);
+        }
     }
 }
 
@@ -234,19 +440,19 @@ struct SimpleGeometries {
 type SimpleGeometriesResources = (Rectangle, RoundedRectangle, EllipseGeom);
 
 impl SimpleGeometries {
-    fn create_device_independent_resources(factory: &Factory) -> (SimpleGeometriesResources) {
-        let rect = (100.0, 100.0, 600.0, 400.0).into();
-        let rectangle = Rectangle::create(factory, &rect).unwrap();
-        let rounded = rounded_rect(rect, 40.0, 40.0);
-        let rounded_rect = RoundedRectangle::create(factory, &rounded).unwrap();
-        let center = (rect.width() / 2.0, rect.height() / 2.0).into();
+    fn create_device_independent_resources() -> SimpleGeometriesResources {
+        let rect: RectF = (100.0, 100.0, 600.0, 400.0).into();
+        let rectangle = Rectangle::create(&rect);
+        let rounded = RoundedRect::new(rect, 40.0, 40.0);
+        let rounded_rect = RoundedRectangle::create(&rounded);
+        let center = Point2F::new(rect.width() / 2.0, rect.height() / 2.0);
         let ellipse = Ellipse::new(center, center.x - 50.0, center.y - 50.0);
-        let ellipse_geom = EllipseGeom::create::<usize>(factory, &ellipse).unwrap();
+        let ellipse_geom = EllipseGeom::create(&ellipse);
         (rectangle, rounded_rect, ellipse_geom)
     }
 
-    fn new(render_target: &HwndRenderTarget, resources: SimpleGeometriesResources) -> Self {
-        let brush = SolidColorBrush::create(render_target).with_opacity(0.5).with_color(COLOR_WHITE).build().unwrap();
+    fn new(canvas: &dyn Canvas, resources: SimpleGeometriesResources) -> Self {
+        let brush = SolidColorBrush::create(canvas).with_opacity(0.5).with_color(COLOR_WHITE).build();
         SimpleGeometries {
             rect: resources.0,
             rounded_rect: resources.1,
@@ -255,11 +461,19 @@ impl SimpleGeometries {
         }
     }
 
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-        render_target.clear(COLOR_BLUE);
-        render_target.draw_geometry(&self.rect, &self.brush, 40.0, None);
-        render_target.draw_geometry(&self.rounded_rect, &self.brush, 50.0, None);
-        render_target.draw_geometry(&self.ellipse, &self.brush, 50.0, None);
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas) {
+        canvas.clear(COLOR_BLUE.into());
+        let shapes: [(&dyn Geometry, f32); 3] = [(&self.rect, 40.0), (&self.rounded_rect, 50.0), (&self.ellipse, 50.0)];
+        // Only the topmost shape under the mouse is highlighted.
+        let mouse = state.mouse_point();
+        let hovered = shapes
+            .iter()
+            .rposition(|&(shape, width)| shape.stroke_contains_point(mouse, width, None, None, DEFAULT_FLATTENING_TOLERANCE));
+        for (i, &(shape, width)) in shapes.iter().enumerate() {
+            let color = if Some(i) == hovered { COLOR_YELLOW } else { COLOR_WHITE };
+            self.brush.set_color(&color.into());
+            canvas.draw_geometry(shape, &self.brush, width, None);
+        }
     }
 }
 
@@ -268,42 +482,34 @@ struct Shapes {
 }
 
 impl Shapes {
-    fn new(render_target: &HwndRenderTarget) -> Self {
-        let brush = SolidColorBrush::create(render_target).with_color(COLOR_BLUE).build().unwrap();
+    fn new(canvas: &dyn Canvas) -> Self {
+        let brush = SolidColorBrush::create(canvas).with_color(COLOR_BLUE).build();
         Shapes {
             brush
         }
     }
-    fn draw(&mut self, _state: &State, render_target: &mut HwndRenderTarget) {
-        render_target.clear(COLOR_BLACK);
-        let size = render_target.get_size();
+    fn draw(&mut self, _state: &State, canvas: &mut dyn Canvas) {
+        canvas.clear(COLOR_BLACK.into());
+        let size = canvas.get_size();
         let offset = 50.0;
-        let rect = (offset, offset, size.width - offset, size.height - offset);
-        let rounded = rounded_rect(rect, 200.0, 200.0);
-        let center = (size.width / 2.0, size.height / 2.0).into();
+        let rect = RectF::new(offset, offset, size.width - offset, size.height - offset);
+        let rounded = RoundedRect::new(rect, 200.0, 200.0);
+        let center = Point2F::new(size.width / 2.0, size.height / 2.0);
         let ellipse = Ellipse::new(center, center.x - offset, center.y - offset);
 
         self.brush.set_color(&COLOR_BLUE.into());
         self.brush.set_opacity(1.0);
-        render_target.fill_rectangle(rect, &self.brush);
+        canvas.fill_rectangle(rect, &self.brush);
 
         self.brush.set_color(&COLOR_BLACK.into());
-        render_target.draw_line((offset, offset), (size.width - offset, size.height - offset), &self.brush, 20.0, None);
+        canvas.draw_line((offset, offset).into(), (size.width - offset, size.height - offset).into(), &self.brush, 20.0, None);
 
         self.brush.set_color(&COLOR_WHITE.into());
         self.brush.set_opacity(0.5);
-        render_target.draw_rectangle(rect, &self.brush, 20.0, None);
-        render_target.draw_rounded_rectangle(rounded, &self.brush, 40.0, None);
+        canvas.draw_rectangle(rect, &self.brush, 20.0, None);
+        canvas.draw_rounded_rectangle(rounded, &self.brush, 40.0, None);
 
         self.brush.set_color(&COLOR_YELLOW.into());
-        render_target.draw_ellipse(ellipse, &self.brush, 40.0, None);
+        canvas.draw_ellipse(ellipse, &self.brush, 40.0, None);
     }
 }
-
-fn rounded_rect<R: Into<RectF>>(rect: R, radius_x: f32, radius_y: f32) -> RoundedRect {
-    RoundedRect(D2D1_ROUNDED_RECT {
-        rect: *rect.into(),
-        radiusX: radius_x,
-        radiusY: radius_y,
-    })
-}
\ No newline at end of file
@@ -1,20 +1,94 @@
-use direct2d::render_target::HwndRenderTarget;
-use gui::State;
-use direct2d::Factory;
+use canvas::Canvas;
+use state::State;
 
-//pub mod brushes;
+pub mod brushes;
 pub mod geometries;
 
 const COLOR_BLUE: u32 = 0x2E_75_E8;
 const COLOR_BLACK: u32 = 0x00_00_00;
 const COLOR_WHITE: u32 = 0xFF_FF_FF;
+#[allow(clippy::mistyped_literal_suffixes)]
 const COLOR_YELLOW: u32 = 0xE5_D3_32;
 const COLOR_RED: u32 = 0xFF_7F_7F;
 const COLOR_GREEN: u32 = 0x7F_FF_7F;
 
-pub trait Example: Sized {
-    fn new() -> Self;
-    fn create_device_resources(&mut self, render_target: &HwndRenderTarget);
-    fn create_device_independent_resources(&mut self, factory: &Factory);
-    fn draw(&mut self, state: &State, render_target: &mut HwndRenderTarget);
-}
\ No newline at end of file
+pub trait Example {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn create_device_resources(&mut self, canvas: &dyn Canvas);
+    fn create_device_independent_resources(&mut self);
+    fn draw(&mut self, state: &State, canvas: &mut dyn Canvas);
+    /// Names of the scenes `draw` can show; the first one is active initially.
+    fn scenes(&self) -> &'static [&'static str];
+    fn scene(&self) -> usize;
+    fn set_scene(&mut self, scene: usize);
+}
+
+/// An entry of the example registry.
+pub struct ExampleInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub create: fn() -> Box<dyn Example>,
+}
+
+fn create<T: Example + 'static>() -> Box<dyn Example> {
+    Box::new(T::new())
+}
+
+/// Every example that can be shown, in the order they are cycled through.
+pub const EXAMPLES: &[ExampleInfo] = &[
+    ExampleInfo {
+        name: "geometries",
+        description: "Shapes, simple geometries and paths with lines, arcs and beziers",
+        create: create::<geometries::Geometries>,
+    },
+    ExampleInfo {
+        name: "brushes",
+        description: "Solid color, linear and radial gradient brushes and styled strokes",
+        create: create::<brushes::Brushes>,
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static ExampleInfo> {
+    EXAMPLES.iter().find(|example| example.name == name)
+}
+
+/// An example and one of its scenes, written `example::scene` on the command line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Selection {
+    pub example: usize,
+    pub scene: usize,
+}
+
+impl Selection {
+    /// Parses `example` or `example::scene`; the example's first scene is the default.
+    pub fn parse(spec: &str) -> Result<Selection, String> {
+        let mut parts = spec.splitn(2, "::");
+        let name = parts.next().unwrap();
+        let example = EXAMPLES
+            .iter()
+            .position(|example| example.name == name)
+            .ok_or_else(|| format!("unknown example `{}`, try `d2d-sandbox list`", name))?;
+        let scene = match parts.next() {
+            None => 0,
+            Some(scene) => (EXAMPLES[example].create)()
+                .scenes()
+                .iter()
+                .position(|name| *name == scene)
+                .ok_or_else(|| format!("`{}` has no scene `{}`, try `d2d-sandbox list`", name, scene))?,
+        };
+        Ok(Selection { example, scene })
+    }
+
+    pub fn info(&self) -> &'static ExampleInfo {
+        &EXAMPLES[self.example]
+    }
+
+    /// Creates the example with the selected scene active.
+    pub fn create(&self) -> Box<dyn Example> {
+        let mut example = (self.info().create)();
+        example.set_scene(self.scene);
+        example
+    }
+}
@@ -0,0 +1,243 @@
+//! Endpoint-parameterized elliptical arcs, as used by `ArcSegment`.
+//!
+//! The conversion to a center parameterization follows the SVG implementation notes
+//! (appendix B.2.4 of SVG 1.1): radii too small to reach the end point are scaled
+//! up uniformly, and an arc to its own start point draws nothing.
+
+use enums::{ArcSize, SweepDirection};
+use math::{ArcSegment, BezierSegment, Point2F};
+
+use std::f32::consts::PI;
+
+/// An arc of the ellipse centered at `center` with the given radii, rotated by
+/// `rotation` radians, from `start_angle` over `sweep_angle` radians. Angles are
+/// measured on the unrotated ellipse and positive sweeps are clockwise on screen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CenterArc {
+    pub center: Point2F,
+    pub radius_x: f32,
+    pub radius_y: f32,
+    pub rotation: f32,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+}
+
+impl CenterArc {
+    /// Point of the ellipse at `angle`.
+    pub fn point_at(&self, angle: f32) -> Point2F {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (x, y) = (self.radius_x * angle.cos(), self.radius_y * angle.sin());
+        Point2F::new(self.center.x + cos * x - sin * y, self.center.y + sin * x + cos * y)
+    }
+
+    /// Cubic beziers that stay within `tolerance` of the arc.
+    pub fn to_beziers(self, tolerance: f32) -> Vec<BezierSegment> {
+        let radius = self.radius_x.max(self.radius_y);
+        // At least one piece per quarter turn, more until the error bound holds.
+        let mut count = ((self.sweep_angle.abs() / (PI / 2.0)).ceil() as usize).max(1);
+        while radius * bezier_error(self.sweep_angle.abs() / count as f32) > tolerance && count < 1024 {
+            count += 1;
+        }
+        let step = self.sweep_angle / count as f32;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        let (sin, cos) = self.rotation.sin_cos();
+        let derivative = |angle: f32| {
+            let (dx, dy) = (-self.radius_x * angle.sin(), self.radius_y * angle.cos());
+            (cos * dx - sin * dy, sin * dx + cos * dy)
+        };
+        (0..count)
+            .map(|i| {
+                let a0 = self.start_angle + step * i as f32;
+                let a1 = a0 + step;
+                let (p0, p3) = (self.point_at(a0), self.point_at(a1));
+                let (d0, d1) = (derivative(a0), derivative(a1));
+                BezierSegment::new(
+                    (p0.x + k * d0.0, p0.y + k * d0.1),
+                    (p3.x - k * d1.0, p3.y - k * d1.1),
+                    p3,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Conservative bound of the distance between a unit circle arc of the given sweep and
+/// its usual cubic approximation, with handles of length `4/3 tan(sweep/4)`. The
+/// ellipse is an affine image of the circle, so scaling by its larger radius bounds it.
+fn bezier_error(sweep: f32) -> f32 {
+    let (sin, cos) = (sweep / 4.0).sin_cos();
+    4.0 / 27.0 * sin.powi(6) / (cos * cos)
+}
+
+/// Finds the ellipse and angles of the arc from `from` described by `arc`.
+///
+/// Returns `None` when the arc is empty, because it ends where it starts, or
+/// degenerates to a straight line, because a radius is zero.
+pub fn to_center(from: Point2F, arc: &ArcSegment) -> Option<CenterArc> {
+    let to = arc.point;
+    let (mut rx, mut ry) = (arc.size.width.abs(), arc.size.height.abs());
+    if from == to || rx == 0.0 || ry == 0.0 {
+        return None;
+    }
+    let rotation = arc.rotation_angle.to_radians();
+    let (sin, cos) = rotation.sin_cos();
+    let half = (from - to) / 2.0;
+    let x1 = cos * half.x + sin * half.y;
+    let y1 = -sin * half.x + cos * half.y;
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let clockwise = arc.sweep_direction == SweepDirection::Clockwise;
+    let large = arc.arc_size == ArcSize::Large;
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut coef = (numerator / denominator).max(0.0).sqrt();
+    if large == clockwise {
+        coef = -coef;
+    }
+    let (cx1, cy1) = (coef * rx * y1 / ry, -coef * ry * x1 / rx);
+    let center = Point2F::new(
+        cos * cx1 - sin * cy1 + (from.x + to.x) / 2.0,
+        sin * cx1 + cos * cy1 + (from.y + to.y) / 2.0,
+    );
+    let start_angle = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+    let end_angle = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+    let mut sweep_angle = end_angle - start_angle;
+    if clockwise && sweep_angle < 0.0 {
+        sweep_angle += 2.0 * PI;
+    } else if !clockwise && sweep_angle > 0.0 {
+        sweep_angle -= 2.0 * PI;
+    }
+    Some(CenterArc {
+        center,
+        radius_x: rx,
+        radius_y: ry,
+        rotation,
+        start_angle,
+        sweep_angle,
+    })
+}
+
+/// Cubic beziers approximating the arc from `from` within `tolerance`. A degenerate
+/// arc becomes a straight bezier to its end point, an empty one yields nothing.
+pub fn to_beziers(from: Point2F, arc: &ArcSegment, tolerance: f32) -> Vec<BezierSegment> {
+    match to_center(from, arc) {
+        Some(center_arc) => {
+            let mut beziers = center_arc.to_beziers(tolerance);
+            // Land exactly on the requested end point.
+            if let Some(last) = beziers.last_mut() {
+                last.point3 = arc.point;
+            }
+            beziers
+        }
+        None if from == arc.point => Vec::new(),
+        None => vec![BezierSegment::new(from, arc.point, arc.point)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geometry::bounds::cubic_at;
+
+    fn arc(to: (f32, f32), radii: (f32, f32), angle: f32, sweep: SweepDirection, size: ArcSize) -> ArcSegment {
+        ArcSegment::new(to, radii, angle, sweep, size)
+    }
+
+    fn assert_near(actual: Point2F, expected: (f32, f32)) {
+        let error = (actual - Point2F::new(expected.0, expected.1)).length();
+        assert!(error < 1e-3, "{:?} is not {:?}", actual, expected);
+    }
+
+    /// The arc starts and ends where it was asked to.
+    fn assert_joins(center_arc: &CenterArc, from: (f32, f32), to: (f32, f32)) {
+        assert_near(center_arc.point_at(center_arc.start_angle), from);
+        assert_near(center_arc.point_at(center_arc.start_angle + center_arc.sweep_angle), to);
+    }
+
+    #[test]
+    fn flags_pick_center_and_sweep() {
+        let below = (50.0, 50.0 * 3f32.sqrt());
+        let cases = [
+            (SweepDirection::Clockwise, ArcSize::Small, below, 1.0),
+            (SweepDirection::Clockwise, ArcSize::Large, (50.0, -below.1), 1.0),
+            (SweepDirection::CounterClockwise, ArcSize::Small, (50.0, -below.1), -1.0),
+            (SweepDirection::CounterClockwise, ArcSize::Large, below, -1.0),
+        ];
+        for &(sweep, size, center, sign) in &cases {
+            let center_arc = to_center(Point2F::origin(), &arc((100.0, 0.0), (100.0, 100.0), 0.0, sweep, size)).unwrap();
+            assert_near(center_arc.center, center);
+            assert_eq!(center_arc.sweep_angle.signum(), sign);
+            // A sixth of the circle, or the rest of it.
+            let expected = if size == ArcSize::Small { PI / 3.0 } else { 5.0 * PI / 3.0 };
+            assert!((center_arc.sweep_angle.abs() - expected).abs() < 1e-4);
+            assert_joins(&center_arc, (0.0, 0.0), (100.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn radii_grow_to_reach_the_end_point() {
+        let center_arc = to_center(Point2F::origin(), &arc((100.0, 0.0), (10.0, 5.0), 0.0, SweepDirection::Clockwise, ArcSize::Small)).unwrap();
+        // Scaled up by the same factor until the end points are opposite.
+        assert!((center_arc.radius_x - 50.0).abs() < 1e-3 && (center_arc.radius_y - 25.0).abs() < 1e-3);
+        assert_near(center_arc.center, (50.0, 0.0));
+        assert!((center_arc.sweep_angle - PI).abs() < 1e-4);
+        assert_joins(&center_arc, (0.0, 0.0), (100.0, 0.0));
+    }
+
+    #[test]
+    fn rotated_ellipses() {
+        let segment = arc((60.0, 80.0), (100.0, 40.0), 30.0, SweepDirection::CounterClockwise, ArcSize::Large);
+        let center_arc = to_center(Point2F::new(10.0, 20.0), &segment).unwrap();
+        assert_eq!((center_arc.radius_x, center_arc.radius_y), (100.0, 40.0));
+        assert!((center_arc.rotation - 30f32.to_radians()).abs() < 1e-6);
+        assert!(center_arc.sweep_angle < -PI);
+        assert_joins(&center_arc, (10.0, 20.0), (60.0, 80.0));
+    }
+
+    #[test]
+    fn degenerate_arcs() {
+        let from = Point2F::new(10.0, 10.0);
+        let to_itself = arc((10.0, 10.0), (50.0, 50.0), 0.0, SweepDirection::Clockwise, ArcSize::Large);
+        assert_eq!(to_center(from, &to_itself), None);
+        assert!(to_beziers(from, &to_itself, 0.25).is_empty());
+        for &radii in &[(0.0, 50.0), (50.0, 0.0)] {
+            let flat = arc((40.0, 10.0), radii, 0.0, SweepDirection::Clockwise, ArcSize::Small);
+            assert_eq!(to_center(from, &flat), None);
+            assert_eq!(to_beziers(from, &flat, 0.25), vec![BezierSegment::new(from, (40.0, 10.0), (40.0, 10.0))]);
+        }
+    }
+
+    #[test]
+    fn beziers_stay_within_tolerance() {
+        let cases = [
+            (arc((100.0, 0.0), (100.0, 100.0), 0.0, SweepDirection::Clockwise, ArcSize::Large), 100.0),
+            (arc((60.0, 80.0), (100.0, 40.0), 30.0, SweepDirection::CounterClockwise, ArcSize::Large), 100.0),
+            (arc((500.0, 0.0), (400.0, 300.0), -60.0, SweepDirection::Clockwise, ArcSize::Small), 400.0),
+        ];
+        for &(ref segment, radius) in &cases {
+            let center_arc = to_center(Point2F::new(10.0, 20.0), segment).unwrap();
+            // The exact arc, sampled finely enough for its chords to be negligible.
+            let exact: Vec<Point2F> = (0..=20000)
+                .map(|i| center_arc.point_at(center_arc.start_angle + center_arc.sweep_angle * i as f32 / 20000.0))
+                .collect();
+            for &tolerance in &[1.0, 0.1, 0.02] {
+                let beziers = center_arc.to_beziers(tolerance);
+                let mut from = center_arc.point_at(center_arc.start_angle);
+                let mut deviation = 0.0f32;
+                for bezier in &beziers {
+                    for i in 0..=50 {
+                        let point = cubic_at(&[from, bezier.point1, bezier.point2, bezier.point3], i as f32 / 50.0);
+                        let nearest = exact.iter().map(|&p| (p - point).length()).fold(f32::MAX, f32::min);
+                        deviation = deviation.max(nearest);
+                    }
+                    from = bezier.point3;
+                }
+                // Allow for the rounding of single precision at this radius.
+                assert!(deviation <= tolerance + radius * 1e-5, "{} > {}", deviation, tolerance);
+            }
+        }
+    }
+}
@@ -0,0 +1,454 @@
+//! Axis-aligned bounds of geometries, like `ID2D1Geometry::GetBounds` and
+//! `GetWidenedBounds`.
+//!
+//! Bounds are exact for the curves themselves: beziers contribute their extrema, found
+//! where the derivative vanishes, and arcs the points where the ellipse is tangent to
+//! an axis, rather than their control points. An empty geometry has the empty
+//! rectangle `(f32::MAX, f32::MAX, -f32::MAX, -f32::MAX)`, as in Direct2D.
+
+use enums::{CapStyle, LineJoin};
+use geometry::arc;
+use geometry::path::{Figure, Segment};
+use geometry::{Geometry, Path};
+use math::*;
+use stroke_style::StrokeStyle;
+
+use std::f32::consts::PI;
+
+/// Bounds of the geometry after applying `transform`.
+pub fn bounds(geometry: &dyn Geometry, transform: Option<&Matrix3x2F>) -> RectF {
+    let mut bounds = Bounds::new();
+    for figure in transformed_path(geometry, transform).figures() {
+        add_figure(&mut bounds, figure);
+    }
+    bounds.rect
+}
+
+/// Bounds of the area the geometry's stroke covers when drawn with `transform`.
+///
+/// The stroke is widened before the transform is applied, like the render target
+/// does. Joins and caps are taken into account, each with its own shape; dashes are
+/// not, so these are the bounds of the solid stroke.
+pub fn widened_bounds(
+    geometry: &dyn Geometry,
+    stroke_width: f32,
+    stroke_style: Option<&StrokeStyle>,
+    transform: Option<&Matrix3x2F>,
+) -> RectF {
+    let half = stroke_width.abs() / 2.0;
+    let matrix = transform.cloned().unwrap_or(Matrix3x2F::IDENTITY);
+    let [[a, b], [c, d], _] = matrix.matrix;
+    // The directions that `matrix` projects onto the x and y axes.
+    let axes = [Vector2F::new(a, c), Vector2F::new(b, d)];
+    let default_style = StrokeStyle::default();
+    let style = stroke_style.unwrap_or(&default_style);
+
+    let mut widened = Bounds::new();
+    for figure in Path::from_geometry(geometry).figures() {
+        for point in stroke_extremes(figure, half, style, &axes) {
+            widened.add(point * matrix);
+        }
+    }
+    widened.rect
+}
+
+fn transformed_path(geometry: &dyn Geometry, transform: Option<&Matrix3x2F>) -> Path {
+    let path = Path::from_geometry(geometry);
+    match transform {
+        Some(matrix) => path.transformed(matrix),
+        None => path,
+    }
+}
+
+struct Bounds {
+    rect: RectF,
+}
+
+impl Bounds {
+    fn new() -> Bounds {
+        Bounds {
+            rect: RectF::new(f32::MAX, f32::MAX, -f32::MAX, -f32::MAX),
+        }
+    }
+
+    fn add(&mut self, point: Point2F) {
+        self.rect.left = self.rect.left.min(point.x);
+        self.rect.top = self.rect.top.min(point.y);
+        self.rect.right = self.rect.right.max(point.x);
+        self.rect.bottom = self.rect.bottom.max(point.y);
+    }
+}
+
+fn add_figure(bounds: &mut Bounds, figure: &Figure) {
+    let axes = [Vector2F::new(1.0, 0.0), Vector2F::new(0.0, 1.0)];
+    bounds.add(figure.start);
+    for (from, segment) in figure.iter() {
+        bounds.add(segment.end_point());
+        for (point, _) in turning_points(from, segment, &axes) {
+            bounds.add(point);
+        }
+    }
+}
+
+/// Points inside the segment where its projection on one of `axes` turns back, with
+/// the unit tangent there. Beziers turn where the derivative of the projection
+/// vanishes, and arcs where the ellipse is tangent to a line across the axis.
+fn turning_points(from: Point2F, segment: &Segment, axes: &[Vector2F; 2]) -> Vec<(Point2F, Vector2F)> {
+    let mut points = Vec::new();
+    for axis in axes {
+        let project = |p: Point2F| p.x * axis.x + p.y * axis.y;
+        match *segment {
+            Segment::Line(_) => {}
+            Segment::Bezier(ref bezier) => {
+                let p = [from, bezier.point1, bezier.point2, bezier.point3];
+                for t in cubic_extrema(project(p[0]), project(p[1]), project(p[2]), project(p[3])) {
+                    let mt = 1.0 - t;
+                    let derivative = (p[1] - p[0]) * (mt * mt) + (p[2] - p[1]) * (2.0 * mt * t) + (p[3] - p[2]) * (t * t);
+                    points.push((cubic_at(&p, t), derivative.unit()));
+                }
+            }
+            Segment::QuadBezier(ref bezier) => {
+                let p = [from, bezier.point1, bezier.point2];
+                if let Some(t) = quad_extremum(project(p[0]), project(p[1]), project(p[2])) {
+                    let mt = 1.0 - t;
+                    let point = Point2F::new(
+                        mt * mt * p[0].x + 2.0 * mt * t * p[1].x + t * t * p[2].x,
+                        mt * mt * p[0].y + 2.0 * mt * t * p[1].y + t * t * p[2].y,
+                    );
+                    points.push((point, ((p[1] - p[0]) * mt + (p[2] - p[1]) * t).unit()));
+                }
+            }
+            Segment::Arc(ref segment) => {
+                if let Some(arc) = arc::to_center(from, segment) {
+                    let (sin, cos) = arc.rotation.sin_cos();
+                    // The axis seen along the radii of the ellipse.
+                    let (along_x, along_y) = (cos * axis.x + sin * axis.y, cos * axis.y - sin * axis.x);
+                    let angle = (arc.radius_y * along_y).atan2(arc.radius_x * along_x);
+                    for &angle in &[angle, angle + PI] {
+                        if within_sweep(angle, arc.start_angle, arc.sweep_angle) {
+                            let (dx, dy) = (-arc.radius_x * angle.sin(), arc.radius_y * angle.cos());
+                            points.push((arc.point_at(angle), Vector2F::new(cos * dx - sin * dy, sin * dx + cos * dy).unit()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    points
+}
+
+/// Whether `angle` lies on the sweep from `start`, in either direction.
+fn within_sweep(angle: f32, start: f32, sweep: f32) -> bool {
+    let turn = 2.0 * PI;
+    let offset = if sweep >= 0.0 { angle - start } else { start - angle };
+    offset.rem_euclid(turn) <= sweep.abs()
+}
+
+/// The point at `t` along the cubic bezier with control points `p`.
+pub fn cubic_at(p: &[Point2F; 4], t: f32) -> Point2F {
+    let mt = 1.0 - t;
+    let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+    Point2F::new(
+        a * p[0].x + b * p[1].x + c * p[2].x + d * p[3].x,
+        a * p[0].y + b * p[1].y + c * p[2].y + d * p[3].y,
+    )
+}
+
+/// Parameters in (0, 1) where one coordinate of a cubic bezier has a zero derivative.
+fn cubic_extrema(p0: f32, p1: f32, p2: f32, p3: f32) -> Vec<f32> {
+    // The derivative is 3 (a t^2 + b t + c).
+    let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+    let b = 2.0 * (p0 - 2.0 * p1 + p2);
+    let c = p1 - p0;
+    let roots = if a.abs() < 1e-6 {
+        if b.abs() < 1e-12 {
+            vec![]
+        } else {
+            vec![-c / b]
+        }
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            vec![]
+        } else {
+            let root = discriminant.sqrt();
+            vec![(-b + root) / (2.0 * a), (-b - root) / (2.0 * a)]
+        }
+    };
+    roots.into_iter().filter(|t| *t > 0.0 && *t < 1.0).collect()
+}
+
+/// Parameter in (0, 1) where one coordinate of a quadratic bezier has a zero derivative.
+fn quad_extremum(p0: f32, p1: f32, p2: f32) -> Option<f32> {
+    let denominator = p0 - 2.0 * p1 + p2;
+    if denominator == 0.0 {
+        return None;
+    }
+    Some((p0 - p1) / denominator).filter(|t| *t > 0.0 && *t < 1.0)
+}
+
+/// Points of the stroke of `figure` that include its extremes along `axes`: the ends
+/// of the sides of every segment, the points where curved sides turn, and the
+/// outermost points of the joins and caps.
+fn stroke_extremes(figure: &Figure, half: f32, style: &StrokeStyle, axes: &[Vector2F; 2]) -> Vec<Point2F> {
+    let segments: Vec<(Point2F, &Segment)> = figure.iter().collect();
+    if segments.is_empty() {
+        return vec![figure.start];
+    }
+    let tangents: Vec<(Vector2F, Vector2F)> = segments.iter().map(|&(from, segment)| tangents(from, segment)).collect();
+    let mut points = Vec::new();
+    let mut sides = |point: Point2F, tangent: Vector2F| {
+        let normal = Vector2F::new(-tangent.y, tangent.x) * half;
+        points.push(point + normal);
+        points.push(point - normal);
+    };
+    for (&(from, segment), &(start, end)) in segments.iter().zip(&tangents) {
+        sides(from, start);
+        sides(segment.end_point(), end);
+        for (point, tangent) in turning_points(from, segment, axes) {
+            sides(point, tangent);
+        }
+    }
+    let closing = figure.is_closed() && figure.end_point() != figure.start;
+    let closing_direction = (figure.start - figure.end_point()).unit();
+    if closing {
+        sides(figure.end_point(), closing_direction);
+        sides(figure.start, closing_direction);
+    }
+
+    for i in 1..segments.len() {
+        add_join(&mut points, segments[i].0, tangents[i - 1].1, tangents[i].0, half, style, axes);
+    }
+    let (first, last) = (tangents[0].0, tangents[tangents.len() - 1].1);
+    if closing {
+        add_join(&mut points, figure.end_point(), last, closing_direction, half, style, axes);
+        add_join(&mut points, figure.start, closing_direction, first, half, style, axes);
+    } else if figure.is_closed() {
+        add_join(&mut points, figure.start, last, first, half, style, axes);
+    } else {
+        add_cap(&mut points, style.get_start_cap(), figure.start, -first, half, axes);
+        add_cap(&mut points, style.get_end_cap(), figure.end_point(), last, half, axes);
+    }
+    points
+}
+
+/// Adds the outermost points of the join at `point` between directions `d0` and `d1`.
+/// Bevels reach no further than the sides of the segments.
+fn add_join(
+    points: &mut Vec<Point2F>,
+    point: Point2F,
+    d0: Vector2F,
+    d1: Vector2F,
+    half: f32,
+    style: &StrokeStyle,
+    axes: &[Vector2F; 2],
+) {
+    match style.get_line_join() {
+        LineJoin::Miter | LineJoin::MiterOrBevel => add_miter(points, point, d0, d1, half, style),
+        // The segments and the round join between them cover the whole pen.
+        LineJoin::Round => points.extend(pen_extremes(point, half, axes)),
+        LineJoin::Bevel => {}
+    }
+}
+
+/// Adds the outermost points of the cap at `point`, facing `outward`. Flat caps reach
+/// no further than the sides of the segment.
+fn add_cap(points: &mut Vec<Point2F>, cap: CapStyle, point: Point2F, outward: Vector2F, half: f32, axes: &[Vector2F; 2]) {
+    match cap {
+        CapStyle::Flat => {}
+        CapStyle::Square => add_square_cap(points, point, outward, half),
+        CapStyle::Triangle => points.push(point + outward * half),
+        CapStyle::Round => points.extend(
+            pen_extremes(point, half, axes)
+                .into_iter()
+                .filter(|p| (*p - point).x * outward.x + (*p - point).y * outward.y >= 0.0),
+        ),
+    }
+}
+
+/// Points of the round pen at `point` that reach furthest along `axes`.
+fn pen_extremes(point: Point2F, half: f32, axes: &[Vector2F; 2]) -> Vec<Point2F> {
+    axes.iter().flat_map(|&axis| vec![point + axis.unit() * half, point - axis.unit() * half]).collect()
+}
+
+/// Unit tangents at the start and the end of the segment, in the drawing direction.
+fn tangents(from: Point2F, segment: &Segment) -> (Vector2F, Vector2F) {
+    let to = segment.end_point();
+    let chord = (to - from).unit();
+    let first_distinct = |points: &[Point2F], origin: Point2F| {
+        points.iter().map(|p| *p - origin).find(|v| v.length() > 0.0).map(|v| v.unit()).unwrap_or(chord)
+    };
+    match *segment {
+        Segment::Line(_) => (chord, chord),
+        Segment::Bezier(ref b) => (
+            first_distinct(&[b.point1, b.point2, b.point3], from),
+            -first_distinct(&[b.point2, b.point1, from], to),
+        ),
+        Segment::QuadBezier(ref b) => (first_distinct(&[b.point1, b.point2], from), -first_distinct(&[b.point1, from], to)),
+        Segment::Arc(ref segment) => match arc::to_center(from, segment) {
+            Some(arc) => {
+                let (sin, cos) = arc.rotation.sin_cos();
+                let sign = arc.sweep_angle.signum();
+                let derivative = |angle: f32| {
+                    let (dx, dy) = (-arc.radius_x * angle.sin(), arc.radius_y * angle.cos());
+                    (Vector2F::new(cos * dx - sin * dy, sin * dx + cos * dy) * sign).unit()
+                };
+                (derivative(arc.start_angle), derivative(arc.start_angle + arc.sweep_angle))
+            }
+            None => (chord, chord),
+        },
+    }
+}
+
+/// Adds the outer corner of the miter at `point` between directions `d0` and `d1`.
+fn add_miter(points: &mut Vec<Point2F>, point: Point2F, d0: Vector2F, d1: Vector2F, half: f32, style: &StrokeStyle) {
+    let cos = (d0.x * d1.x + d0.y * d1.y).clamp(-1.0, 1.0);
+    let cross = d0.x * d1.y - d0.y * d1.x;
+    if cross.abs() < 1e-6 && cos > 0.0 {
+        return;
+    }
+    // Outward bisector of the corner and the distance of the miter tip along it.
+    let bisector = (d0 - d1).unit();
+    let ratio = (2.0 / (1.0 + cos)).sqrt();
+    let limit = style.get_miter_limit().max(1.0);
+    if ratio <= limit {
+        points.push(point + bisector * (half * ratio));
+    } else if style.get_line_join() == LineJoin::Miter {
+        // Clipped at the miter limit: the two ends of the clipping line.
+        let along = half * limit;
+        let tip = half * ratio;
+        let spread = (tip - along) * ((1.0 + cos) / (1.0 - cos)).sqrt();
+        let across = Vector2F::new(-bisector.y, bisector.x) * spread;
+        let center = point + bisector * along;
+        points.push(center + across);
+        points.push(center - across);
+    }
+}
+
+fn add_square_cap(points: &mut Vec<Point2F>, point: Point2F, outward: Vector2F, half: f32) {
+    let normal = Vector2F::new(-outward.y, outward.x) * half;
+    let reach = point + outward * half;
+    points.push(reach + normal);
+    points.push(reach - normal);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enums::{FigureBegin, FigureEnd, FillMode};
+    use geometry::{Ellipse, GeometryGroup, Rectangle};
+
+    fn assert_close(actual: RectF, expected: RectF) {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-2;
+        assert!(
+            close(actual.left, expected.left)
+                && close(actual.top, expected.top)
+                && close(actual.right, expected.right)
+                && close(actual.bottom, expected.bottom),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn curves_use_extrema_not_control_points() {
+        let mut path = Path::new();
+        path.open()
+            .begin_figure((0.0, 0.0), FigureBegin::Filled, FigureEnd::Open)
+            .add_bezier(&BezierSegment::new((0.0, 100.0), (100.0, 100.0), (100.0, 0.0)))
+            .end();
+        assert_close(bounds(&path, None), RectF::new(0.0, 0.0, 100.0, 75.0));
+    }
+
+    #[test]
+    fn rotated_ellipse() {
+        let ellipse = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 20.0, 10.0));
+        let rotation = Matrix3x2F::rotation(PI / 2.0, (0.0, 0.0));
+        assert_close(bounds(&ellipse, Some(&rotation)), RectF::new(-10.0, -20.0, 10.0, 20.0));
+    }
+
+    #[test]
+    fn groups_and_transformed_geometries() {
+        let ellipse = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 20.0, 10.0));
+        let rect = Rectangle::create(&RectF::new(30.0, 0.0, 40.0, 50.0));
+        let group = GeometryGroup::create(FillMode::Winding, &[&ellipse, &rect]);
+        assert_close(bounds(&group, None), RectF::new(-20.0, -10.0, 40.0, 50.0));
+        // The geometry's own transform applies before the one it is drawn with.
+        let turned = group.transformed(&Matrix3x2F::rotation(PI / 2.0, (0.0, 0.0)));
+        let shift = Matrix3x2F::translation((100.0, 0.0));
+        assert_close(bounds(&turned, Some(&shift)), RectF::new(50.0, -20.0, 110.0, 40.0));
+        assert_eq!(turned.get_fill_mode(), FillMode::Winding);
+    }
+
+    #[test]
+    fn widened_bounds_include_miters() {
+        let widened = widened_bounds(&chevron(), 10.0, None, None);
+        // The flat ends are offset along the normals of the sides, (2, 1) / sqrt(5)
+        // times the half width, and the miter tip sits above the apex by the half
+        // width over sin(atan(1/2)).
+        let (across, along) = (5.0 * 2.0 / 5f32.sqrt(), 5.0 / 5f32.sqrt());
+        assert_close(widened, RectF::new(-across, -5.0 * 5f32.sqrt(), 100.0 + across, 100.0 + along));
+        let bevel = StrokeStyle::create().with_line_join(LineJoin::Bevel).build();
+        assert_close(widened_bounds(&chevron(), 10.0, Some(&bevel), None), RectF::new(-across, -along, 100.0 + across, 100.0 + along));
+        let round = StrokeStyle::create().with_line_join(LineJoin::Round).build();
+        assert_close(widened_bounds(&chevron(), 10.0, Some(&round), None), RectF::new(-across, -5.0, 100.0 + across, 100.0 + along));
+    }
+
+    #[test]
+    fn widened_bounds_follow_caps() {
+        let caps = |cap| {
+            let style = StrokeStyle::create().with_start_cap(cap).with_end_cap(cap).with_line_join(LineJoin::Bevel).build();
+            widened_bounds(&chevron(), 10.0, Some(&style), None)
+        };
+        let (across, along) = (5.0 * 2.0 / 5f32.sqrt(), 5.0 / 5f32.sqrt());
+        // The ends point down and out, along (-1, 2) / sqrt(5) on the left.
+        assert_close(caps(CapStyle::Flat), RectF::new(-across, -along, 100.0 + across, 100.0 + along));
+        assert_close(caps(CapStyle::Triangle), RectF::new(-across, -along, 100.0 + across, 100.0 + across));
+        assert_close(caps(CapStyle::Round), RectF::new(-5.0, -along, 105.0, 105.0));
+        assert_close(caps(CapStyle::Square), RectF::new(-along - across, -along, 100.0 + along + across, 100.0 + across + along));
+    }
+
+    #[test]
+    fn widened_bounds_of_curves_and_transforms() {
+        // Curved sides reach half the width beyond the curve where it turns.
+        let ellipse = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 20.0, 10.0));
+        assert_close(widened_bounds(&ellipse, 4.0, None, None), RectF::new(-22.0, -12.0, 22.0, 12.0));
+        // The stroke is widened first, so a stretch stretches the width with it.
+        let stretch = Matrix3x2F::scale((3.0, 1.0), (0.0, 0.0));
+        assert_close(widened_bounds(&ellipse, 4.0, None, Some(&stretch)), RectF::new(-66.0, -12.0, 66.0, 12.0));
+        let mut line = Path::new();
+        line.open().begin_figure((0.0, 0.0), FigureBegin::Hollow, FigureEnd::Open).add_line((10.0, 10.0)).end();
+        let round = StrokeStyle::create().with_start_cap(CapStyle::Round).with_end_cap(CapStyle::Round).build();
+        let turn = Matrix3x2F::rotation(PI / 4.0, (0.0, 0.0));
+        let length = 200f32.sqrt();
+        assert_close(widened_bounds(&line, 2.0, Some(&round), Some(&turn)), RectF::new(-1.0, -1.0, 1.0, length + 1.0));
+    }
+
+    #[test]
+    fn widened_bounds_of_groups_and_transformed_geometries() {
+        // Each source is widened, and the miters at the square corners reach half the
+        // width out both ways.
+        let a = Rectangle::create(&RectF::new(0.0, 0.0, 100.0, 100.0));
+        let b = Rectangle::create(&RectF::new(50.0, 50.0, 150.0, 150.0));
+        let group = GeometryGroup::create(FillMode::Winding, &[&a, &b]);
+        assert_close(widened_bounds(&group, 10.0, None, None), RectF::new(-5.0, -5.0, 155.0, 155.0));
+        // Its own transform scales the outline and not the width; the one it is drawn
+        // with scales both.
+        let small = Rectangle::create(&RectF::new(0.0, 0.0, 10.0, 10.0));
+        let scaled = small.transformed(&Matrix3x2F::scale((10.0, 10.0), (0.0, 0.0)));
+        assert_close(widened_bounds(&scaled, 10.0, None, None), RectF::new(-5.0, -5.0, 105.0, 105.0));
+        let stretch = Matrix3x2F::scale((2.0, 1.0), (0.0, 0.0));
+        assert_close(widened_bounds(&scaled, 10.0, None, Some(&stretch)), RectF::new(-10.0, -5.0, 210.0, 105.0));
+    }
+
+    /// An upside down V, drawn left to right.
+    fn chevron() -> Path {
+        let mut path = Path::new();
+        path.open()
+            .begin_figure((0.0, 100.0), FigureBegin::Hollow, FigureEnd::Open)
+            .add_lines(&[(50.0, 0.0).into(), (100.0, 100.0).into()])
+            .end();
+        path
+    }
+}
@@ -0,0 +1,362 @@
+//! Boolean operations on geometries, like `ID2D1Geometry::CombineWithGeometry`.
+//!
+//! Both geometries are flattened within the tolerance and every edge is split where
+//! it meets another one. A piece of edge belongs to the outline of the result when the
+//! combined region lies on one side of it only; those pieces are turned so the region
+//! is on their right, which traces outer figures clockwise, and chained back into
+//! closed figures. Curves therefore come out as lines.
+
+use enums::{CombineMode, FigureBegin, FigureEnd, FillMode};
+use geometry::flatten;
+use geometry::{Geometry, Path};
+use math::*;
+
+use std::collections::{HashMap, HashSet};
+
+/// Points are combined in double precision, which keeps the crossings of nearly
+/// parallel edges stable.
+type Point = (f64, f64);
+
+/// Relative distance under which two points are considered the same.
+const SNAP: f64 = 1e-9;
+
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    from: Point,
+    to: Point,
+}
+
+/// Combines the fill of `geometry` with the fill of `input`, drawn with
+/// `input_transform`.
+pub fn combine(
+    geometry: &dyn Geometry,
+    input: &dyn Geometry,
+    mode: CombineMode,
+    input_transform: Option<&Matrix3x2F>,
+    tolerance: f32,
+) -> Path {
+    let first = polygons(geometry, None, tolerance);
+    let second = polygons(input, input_transform, tolerance);
+    let edges: Vec<Edge> = first.iter().chain(&second).flat_map(|polygon| polygon_edges(polygon)).collect();
+    let (first_mode, second_mode) = (geometry.get_fill_mode(), input.get_fill_mode());
+    trace(&edges, |point| {
+        let (a, b) = (fills(first_mode, winding(&first, point)), fills(second_mode, winding(&second, point)));
+        match mode {
+            CombineMode::Union => a || b,
+            CombineMode::Intersect => a && b,
+            CombineMode::Xor => a != b,
+            CombineMode::Exclude => a && !b,
+        }
+    })
+}
+
+/// The outline of the area the polygons cover with the non-zero fill rule, which is
+/// their union when they are all wound the same way.
+pub fn union(polygons: &[Vec<Point2F>]) -> Path {
+    let polygons: Vec<Vec<Point>> = polygons
+        .iter()
+        .map(|polygon| polygon.iter().map(|point| (f64::from(point.x), f64::from(point.y))).collect())
+        .collect();
+    let edges: Vec<Edge> = polygons.iter().flat_map(|polygon| polygon_edges(polygon)).collect();
+    trace(&edges, |point| winding(&polygons, point) != 0)
+}
+
+/// The outline between the points `inside` accepts and the others, given every edge
+/// where that can change.
+fn trace<F: Fn(Point) -> bool>(edges: &[Edge], inside: F) -> Path {
+    let scale = edges
+        .iter()
+        .flat_map(|edge| vec![edge.from.0.abs(), edge.from.1.abs()])
+        .fold(1.0, f64::max);
+    // Far enough from a piece to be clear of rounding, close enough to be clear of
+    // any other edge.
+    let offset = scale * 1e-7;
+    let mut seen = HashSet::new();
+    let mut outline = Vec::new();
+    for piece in split(edges) {
+        let (dx, dy) = (piece.to.0 - piece.from.0, piece.to.1 - piece.from.1);
+        let length = dx.hypot(dy);
+        let (nx, ny) = (-dy / length * offset, dx / length * offset);
+        let middle = ((piece.from.0 + piece.to.0) / 2.0, (piece.from.1 + piece.to.1) / 2.0);
+        let right = inside((middle.0 + nx, middle.1 + ny));
+        let left = inside((middle.0 - nx, middle.1 - ny));
+        let edge = match (left, right) {
+            (false, true) => piece,
+            (true, false) => Edge { from: piece.to, to: piece.from },
+            _ => continue,
+        };
+        // Edges shared by two polygons would otherwise come out twice.
+        if seen.insert((key(edge.from), key(edge.to))) {
+            outline.push(edge);
+        }
+    }
+    chain(&outline)
+}
+
+/// The filled figures of the geometry as closed polygons.
+fn polygons(geometry: &dyn Geometry, transform: Option<&Matrix3x2F>, tolerance: f32) -> Vec<Vec<Point>> {
+    flatten::flatten_transformed(geometry, transform, tolerance)
+        .into_iter()
+        .filter(|polyline| polyline.filled)
+        .map(|polyline| {
+            let mut points: Vec<Point> = polyline.points.iter().map(|point| (f64::from(point.x), f64::from(point.y))).collect();
+            if points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            points
+        })
+        .filter(|points| points.len() >= 3)
+        .collect()
+}
+
+fn polygon_edges(polygon: &[Point]) -> Vec<Edge> {
+    (0..polygon.len())
+        .map(|i| Edge {
+            from: polygon[i],
+            to: polygon[(i + 1) % polygon.len()],
+        })
+        .filter(|edge| edge.from != edge.to)
+        .collect()
+}
+
+fn fills(mode: FillMode, winding: i32) -> bool {
+    match mode {
+        FillMode::Alternate => winding % 2 != 0,
+        FillMode::Winding => winding != 0,
+    }
+}
+
+/// How many times the polygons wind around `point`, counting clockwise turns.
+fn winding(polygons: &[Vec<Point>], point: Point) -> i32 {
+    let mut winding = 0;
+    for polygon in polygons {
+        for (i, &p0) in polygon.iter().enumerate() {
+            let p1 = polygon[(i + 1) % polygon.len()];
+            if (p0.1 > point.1) != (p1.1 > point.1) {
+                let x = p0.0 + (point.1 - p0.1) / (p1.1 - p0.1) * (p1.0 - p0.0);
+                if x > point.0 {
+                    winding += if p1.1 > p0.1 { 1 } else { -1 };
+                }
+            }
+        }
+    }
+    winding
+}
+
+/// Cuts every edge where other edges cross or touch it.
+fn split(edges: &[Edge]) -> Vec<Edge> {
+    let mut cuts: Vec<Vec<(f64, Point)>> = vec![Vec::new(); edges.len()];
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            for (ti, tj, point) in intersections(edges[i], edges[j]) {
+                if ti > 0.0 && ti < 1.0 {
+                    cuts[i].push((ti, point));
+                }
+                if tj > 0.0 && tj < 1.0 {
+                    cuts[j].push((tj, point));
+                }
+            }
+        }
+    }
+    let mut pieces = Vec::new();
+    for (edge, mut cuts) in edges.iter().zip(cuts) {
+        cuts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let mut from = edge.from;
+        for to in cuts.into_iter().map(|(_, point)| point).chain(Some(edge.to)) {
+            if to != from {
+                pieces.push(Edge { from, to });
+                from = to;
+            }
+        }
+    }
+    pieces
+}
+
+/// Where two edges meet, as the parameter along each and the shared point. Points
+/// near an end of either edge are moved onto that end, so the pieces cut at them
+/// meet exactly.
+fn intersections(a: Edge, b: Edge) -> Vec<(f64, f64, Point)> {
+    let (da, db) = (sub(a.to, a.from), sub(b.to, b.from));
+    let (la, lb) = (length(da), length(db));
+    let ((a0, a1), (b0, b1)) = ((a.from.0.min(a.to.0), a.from.0.max(a.to.0)), (b.from.0.min(b.to.0), b.from.0.max(b.to.0)));
+    let ((c0, c1), (d0, d1)) = ((a.from.1.min(a.to.1), a.from.1.max(a.to.1)), (b.from.1.min(b.to.1), b.from.1.max(b.to.1)));
+    let margin = SNAP * (la + lb);
+    if a0 > b1 + margin || b0 > a1 + margin || c0 > d1 + margin || d0 > c1 + margin {
+        return Vec::new();
+    }
+    let offset = sub(b.from, a.from);
+    let denominator = cross(da, db);
+    if denominator.abs() <= SNAP * la * lb {
+        // Parallel: collinear edges overlap where an end of one lies on the other.
+        if cross(offset, da).abs() > SNAP * la * (la + lb) {
+            return Vec::new();
+        }
+        let along = |point: Point, edge: Edge, direction: Point, length: f64| {
+            let t = dot(sub(point, edge.from), direction) / (length * length);
+            Some(t).filter(|t| *t > 0.0 && *t < 1.0)
+        };
+        let mut found = Vec::new();
+        for &(point, tb) in &[(b.from, 0.0), (b.to, 1.0)] {
+            if let Some(ta) = along(point, a, da, la) {
+                found.push((ta, tb, point));
+            }
+        }
+        for &(point, ta) in &[(a.from, 0.0), (a.to, 1.0)] {
+            if let Some(tb) = along(point, b, db, lb) {
+                found.push((ta, tb, point));
+            }
+        }
+        return found;
+    }
+    let (mut ta, mut tb) = (cross(offset, db) / denominator, cross(offset, da) / denominator);
+    let outside = |t: f64, length: f64| t * length < -margin || (t - 1.0) * length > margin;
+    if outside(ta, la) || outside(tb, lb) {
+        return Vec::new();
+    }
+    let mut point = (a.from.0 + da.0 * ta, a.from.1 + da.1 * ta);
+    let near = |p: Point, q: Point| length(sub(p, q)) <= margin;
+    if near(point, a.from) || near(point, a.to) {
+        let end = if near(point, a.from) { (a.from, 0.0) } else { (a.to, 1.0) };
+        point = end.0;
+        ta = end.1;
+    }
+    if near(point, b.from) || near(point, b.to) {
+        let end = if near(point, b.from) { (b.from, 0.0) } else { (b.to, 1.0) };
+        point = end.0;
+        tb = end.1;
+    }
+    vec![(ta, tb, point)]
+}
+
+/// Links the outline edges end to start into closed figures.
+fn chain(edges: &[Edge]) -> Path {
+    let mut starting: HashMap<(u64, u64), Vec<usize>> = HashMap::new();
+    for (i, edge) in edges.iter().enumerate() {
+        starting.entry(key(edge.from)).or_default().push(i);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut path = Path::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let start = edges[first].from;
+        let mut points = vec![start];
+        let mut at = edges[first].to;
+        while at != start {
+            push_vertex(&mut points, at);
+            let next = starting.get(&key(at)).and_then(|candidates| candidates.iter().cloned().find(|&i| !used[i]));
+            match next {
+                Some(i) => {
+                    used[i] = true;
+                    at = edges[i].to;
+                }
+                // Only rounding can leave an outline open; close it where it stops.
+                None => break,
+            }
+        }
+        while points.len() >= 3 && collinear(points[points.len() - 2], points[points.len() - 1], points[0]) {
+            points.pop();
+        }
+        while points.len() >= 3 && collinear(points[points.len() - 1], points[0], points[1]) {
+            points.remove(0);
+        }
+        if points.len() < 3 {
+            continue;
+        }
+        let points: Vec<Point2F> = points.iter().map(|&(x, y)| Point2F::new(x as f32, y as f32)).collect();
+        path.open()
+            .begin_figure(points[0], FigureBegin::Filled, FigureEnd::Closed)
+            .add_lines(&points[1..])
+            .end();
+    }
+    path
+}
+
+/// Appends `point`, dropping the previous vertex if it lies on a straight run.
+fn push_vertex(points: &mut Vec<Point>, point: Point) {
+    if points.len() >= 2 && collinear(points[points.len() - 2], points[points.len() - 1], point) {
+        points.pop();
+    }
+    points.push(point);
+}
+
+/// Whether `b` lies on the straight way from `a` to `c`.
+fn collinear(a: Point, b: Point, c: Point) -> bool {
+    let (ab, bc) = (sub(b, a), sub(c, b));
+    cross(ab, bc).abs() <= SNAP * length(ab) * length(bc) && dot(ab, bc) > 0.0
+}
+
+fn key(point: Point) -> (u64, u64) {
+    // Adding zero turns -0.0 into 0.0, which compares equal but has other bits.
+    ((point.0 + 0.0).to_bits(), (point.1 + 0.0).to_bits())
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn cross(a: Point, b: Point) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn dot(a: Point, b: Point) -> f64 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+fn length(v: Point) -> f64 {
+    v.0.hypot(v.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geometry::fixtures::area;
+    use geometry::flatten::DEFAULT_FLATTENING_TOLERANCE as TOLERANCE;
+    use geometry::{Ellipse, Rectangle};
+
+    #[test]
+    fn combines_overlapping_squares() {
+        let a = Rectangle::create(&RectF::new(0.0, 0.0, 100.0, 100.0));
+        let b = Rectangle::create(&RectF::new(50.0, 50.0, 150.0, 150.0));
+        let combined = |mode| a.combine_with_geometry(&b, mode, None, TOLERANCE);
+        assert_eq!(area(&combined(CombineMode::Union)), 17500.0);
+        assert_eq!(area(&combined(CombineMode::Intersect)), 2500.0);
+        assert_eq!(area(&combined(CombineMode::Xor)), 15000.0);
+        assert_eq!(area(&combined(CombineMode::Exclude)), 7500.0);
+        // The union is a single figure with only its eight corners left; closing the
+        // figure draws the last side.
+        let union = combined(CombineMode::Union);
+        assert_eq!(union.get_figure_count(), 1);
+        assert_eq!(union.get_segment_count(), 7);
+    }
+
+    #[test]
+    fn shared_edges_merge() {
+        let a = Rectangle::create(&RectF::new(0.0, 0.0, 100.0, 100.0));
+        let b = Rectangle::create(&RectF::new(100.0, 0.0, 200.0, 100.0));
+        let union = a.combine_with_geometry(&b, CombineMode::Union, None, TOLERANCE);
+        assert_eq!(union.get_segment_count(), 3);
+        assert_eq!(area(&union), 20000.0);
+        let intersection = a.combine_with_geometry(&b, CombineMode::Intersect, None, TOLERANCE);
+        assert_eq!(intersection.get_figure_count(), 0);
+    }
+
+    #[test]
+    fn combines_curves_with_transform() {
+        let circle = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 50.0, 50.0));
+        let shift = Matrix3x2F::translation((200.0, 0.0));
+        // A moved copy of the circle is disjoint, so excluding it changes nothing and
+        // the xor holds both circles.
+        let exclude = circle.combine_with_geometry(&circle, CombineMode::Exclude, Some(&shift), TOLERANCE);
+        let xor = circle.combine_with_geometry(&circle, CombineMode::Xor, Some(&shift), TOLERANCE);
+        let full = ::std::f32::consts::PI * 2500.0;
+        assert!((area(&exclude) - full).abs() < full * 0.01);
+        assert_eq!(xor.get_figure_count(), 2);
+        assert!(xor.fill_contains_point(Point2F::new(200.0, 0.0), None, TOLERANCE));
+        // The circle with itself excludes to nothing.
+        let empty = circle.combine_with_geometry(&circle, CombineMode::Exclude, None, TOLERANCE);
+        assert_eq!(empty.get_figure_count(), 0);
+    }
+}
@@ -0,0 +1,146 @@
+//! Relations between the fills of two geometries, like
+//! `ID2D1Geometry::CompareWithGeometry`.
+//!
+//! The fills are compared by area: geometries whose intersection has no area are
+//! disjoint, and one contains the other when their intersection covers all of it.
+//! Flattening moves curves by up to the tolerance, so area within the tolerance times
+//! the length of the flattened curves is ignored; lines stay where they are, and only
+//! leave `RELATIVE_ERROR` of the area to rounding.
+
+use enums::{CombineMode, FigureBegin, FigureEnd, GeometryRelation};
+use geometry::combine;
+use geometry::flatten;
+use geometry::path::Segment;
+use geometry::tessellate;
+use geometry::{Geometry, Path};
+use math::*;
+
+/// Fraction of a fill's area the comparison leaves to rounding.
+const RELATIVE_ERROR: f64 = 1e-6;
+
+/// How the fill of `geometry` relates to the fill of `input` drawn with
+/// `input_transform`.
+pub fn compare(
+    geometry: &dyn Geometry,
+    input: &dyn Geometry,
+    input_transform: Option<&Matrix3x2F>,
+    tolerance: f32,
+) -> GeometryRelation {
+    let (bounds, input_bounds) = (geometry.bounds(None), input.bounds(input_transform));
+    if bounds.right < input_bounds.left
+        || input_bounds.right < bounds.left
+        || bounds.bottom < input_bounds.top
+        || input_bounds.bottom < bounds.top
+    {
+        return GeometryRelation::Disjoint;
+    }
+    let area = fill_area(geometry, None, tolerance);
+    let input_area = fill_area(input, input_transform, tolerance);
+    let error = curve_length(geometry, None, tolerance) * f64::from(tolerance) + area * RELATIVE_ERROR;
+    let input_error = curve_length(input, input_transform, tolerance) * f64::from(tolerance) + input_area * RELATIVE_ERROR;
+    let shared = combine::combine(geometry, input, CombineMode::Intersect, input_transform, tolerance);
+    let shared = fill_area(&shared, None, tolerance);
+    if shared <= error.min(input_error) {
+        GeometryRelation::Disjoint
+    } else if area - shared <= error {
+        GeometryRelation::IsContained
+    } else if input_area - shared <= input_error {
+        GeometryRelation::Contains
+    } else {
+        GeometryRelation::Overlap
+    }
+}
+
+/// Length of the curves and arcs along the outline of the fill, flattened as they are
+/// when filling.
+fn curve_length(geometry: &dyn Geometry, transform: Option<&Matrix3x2F>, tolerance: f32) -> f64 {
+    let mut curves = Path::new();
+    {
+        let mut builder = curves.open();
+        for figure in Path::from_geometry(geometry).figures().iter().filter(|figure| figure.is_filled()) {
+            for (from, segment) in figure.iter().filter(|&(_, segment)| !matches!(*segment, Segment::Line(_))) {
+                let curve = builder.begin_figure(from, FigureBegin::Hollow, FigureEnd::Open);
+                builder = match *segment {
+                    Segment::Line(_) => curve,
+                    Segment::Bezier(ref bezier) => curve.add_bezier(bezier),
+                    Segment::QuadBezier(ref bezier) => curve.add_quadratic_bezier(bezier),
+                    Segment::Arc(ref arc) => curve.add_arc(arc),
+                }
+                .end();
+            }
+        }
+    }
+    flatten::flatten_transformed(&curves, transform, tolerance)
+        .iter()
+        .flat_map(|polyline| polyline.points.windows(2).map(|pair| f64::from((pair[1] - pair[0]).length())))
+        .sum()
+}
+
+/// Area of the fill, summed in double precision.
+fn fill_area(geometry: &dyn Geometry, transform: Option<&Matrix3x2F>, tolerance: f32) -> f64 {
+    tessellate::tessellate(geometry, transform, tolerance)
+        .iter()
+        .map(|triangle| {
+            let (a, b) = (triangle.point2 - triangle.point1, triangle.point3 - triangle.point1);
+            (f64::from(a.x) * f64::from(b.y) - f64::from(a.y) * f64::from(b.x)) / 2.0
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enums::FillMode;
+    use geometry::fixtures::nested_squares;
+    use geometry::flatten::DEFAULT_FLATTENING_TOLERANCE as TOLERANCE;
+    use geometry::{Ellipse, Rectangle, RoundedRectangle};
+
+    #[test]
+    fn rectangles() {
+        let rect = Rectangle::create(&RectF::new(0.0, 0.0, 100.0, 100.0));
+        let relation = |left, top, right, bottom| {
+            rect.compare_with_geometry(&Rectangle::create(&RectF::new(left, top, right, bottom)), None, TOLERANCE)
+        };
+        assert_eq!(relation(200.0, 0.0, 300.0, 100.0), GeometryRelation::Disjoint);
+        // Touching along an edge shares no area.
+        assert_eq!(relation(100.0, 0.0, 200.0, 100.0), GeometryRelation::Disjoint);
+        assert_eq!(relation(10.0, 10.0, 90.0, 90.0), GeometryRelation::Contains);
+        assert_eq!(relation(-10.0, -10.0, 100.0, 100.0), GeometryRelation::IsContained);
+        assert_eq!(relation(50.0, 50.0, 150.0, 150.0), GeometryRelation::Overlap);
+        // Lines are not flattened, so slivers far thinner than the tolerance still count.
+        assert_eq!(relation(99.0, 0.0, 199.0, 100.0), GeometryRelation::Overlap);
+        assert_eq!(relation(0.0, 0.0, 100.5, 100.0), GeometryRelation::IsContained);
+        let wider = Rectangle::create(&RectF::new(0.0, 0.0, 100.5, 100.0));
+        assert_eq!(wider.compare_with_geometry(&rect, None, TOLERANCE), GeometryRelation::Contains);
+        assert_eq!(relation(99.9, 0.0, 199.9, 100.0), GeometryRelation::Overlap);
+    }
+
+    #[test]
+    fn curves_transforms_and_fill_modes() {
+        let ellipse = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 40.0, 20.0));
+        let rounded = RoundedRectangle::create(&RoundedRect::new(RectF::new(0.0, 0.0, 100.0, 60.0), 20.0, 20.0));
+        let center = Matrix3x2F::translation((50.0, 30.0));
+        assert_eq!(rounded.compare_with_geometry(&ellipse, Some(&center), TOLERANCE), GeometryRelation::Contains);
+        let corner = Matrix3x2F::translation((-20.0, -5.0));
+        assert_eq!(rounded.compare_with_geometry(&ellipse, Some(&corner), TOLERANCE), GeometryRelation::Overlap);
+        // Within the bounds, but clear of the rounded corner.
+        let clear = Matrix3x2F::translation((-28.0, -14.0));
+        assert_eq!(rounded.compare_with_geometry(&ellipse, Some(&clear), TOLERANCE), GeometryRelation::Disjoint);
+
+        // The hole of a frame filled with the alternate rule is outside of it.
+        let frame = nested_squares(FillMode::Alternate, 20.0);
+        let inner = Rectangle::create(&RectF::new(30.0, 30.0, 70.0, 70.0));
+        assert_eq!(inner.compare_with_geometry(&frame, None, TOLERANCE), GeometryRelation::Disjoint);
+        assert_eq!(frame.compare_with_geometry(&inner, None, TOLERANCE), GeometryRelation::Disjoint);
+    }
+
+    #[test]
+    fn flattening_error_scales_with_the_outline() {
+        // A small circle and the same circle turned are flattened at different points,
+        // which leaves slivers along the whole outline that are far more than a fixed
+        // fraction of its area.
+        let circle = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 10.0, 10.0));
+        let turn = Matrix3x2F::rotation(0.2, (0.0, 0.0));
+        assert_eq!(circle.compare_with_geometry(&circle, Some(&turn), TOLERANCE), GeometryRelation::IsContained);
+    }
+}
@@ -0,0 +1,224 @@
+//! Laying dash patterns out along figures.
+//!
+//! The pattern runs along the flattened figures in drawing order without restarting,
+//! so it carries on across segment and figure boundaries as well as around the closing
+//! side of closed figures. Every dash becomes an open figure of its own; dashes get
+//! the dash cap at both ends, except where they start or end an open figure.
+
+use enums::{CapStyle, FigureBegin, FigureEnd};
+use geometry::flatten::{self, Polyline};
+use geometry::{Geometry, Path};
+use math::*;
+use stroke_style::StrokeStyle;
+
+/// One dash of a dashed stroke, with the caps it is drawn with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dash {
+    pub points: Vec<Point2F>,
+    /// Direction of the figure where the dash starts, which orients the caps of a
+    /// dash of zero length.
+    pub direction: Vector2F,
+    pub start_cap: CapStyle,
+    pub end_cap: CapStyle,
+}
+
+/// The dashes of the geometry's stroke as open figures, flattened within `tolerance`.
+/// A solid stroke leaves the figures whole.
+pub fn dash(geometry: &dyn Geometry, stroke_width: f32, stroke_style: Option<&StrokeStyle>, tolerance: f32) -> Path {
+    let default_style = StrokeStyle::default();
+    let style = stroke_style.unwrap_or(&default_style);
+    let dashes = match dashes(&flatten::flatten(geometry, tolerance), stroke_width, style) {
+        Some(dashes) => dashes,
+        None => return flatten::simplify(geometry, tolerance),
+    };
+    let mut path = Path::new();
+    for dash in dashes {
+        // A dash of zero length keeps a segment, so that its caps are still drawn.
+        let rest = if dash.points.len() > 1 { &dash.points[1..] } else { &dash.points[..] };
+        path.open().begin_figure(dash.points[0], FigureBegin::Hollow, FigureEnd::Open).add_lines(rest).end();
+    }
+    path
+}
+
+/// The dashes along the given figures, or `None` when the style draws solid strokes.
+pub fn dashes(polylines: &[Polyline], stroke_width: f32, style: &StrokeStyle) -> Option<Vec<Dash>> {
+    let width = stroke_width.abs();
+    let mut pattern: Vec<f32> = style.dash_pattern().iter().map(|length| length.abs() * width).collect();
+    if !pattern.len().is_multiple_of(2) {
+        // Repeated, an odd pattern swaps dashes and gaps every other time.
+        pattern.extend_from_slice(&pattern.clone());
+    }
+    let period: f32 = pattern.iter().sum();
+    if period <= 0.0 || !period.is_finite() {
+        return None;
+    }
+    let mut dasher = Dasher::new(&pattern, style.get_dash_offset() * width);
+    let mut dashes = Vec::new();
+    for polyline in polylines {
+        dasher.figure(polyline, style, &mut dashes);
+    }
+    Some(dashes)
+}
+
+/// Position in the dash pattern, carried from one figure to the next.
+struct Dasher<'a> {
+    pattern: &'a [f32],
+    index: usize,
+    /// Length left of the current dash or gap.
+    remaining: f32,
+}
+
+impl<'a> Dasher<'a> {
+    fn new(pattern: &'a [f32], offset: f32) -> Dasher<'a> {
+        // A positive offset starts that far into the pattern.
+        let period: f32 = pattern.iter().sum();
+        let mut position = offset.rem_euclid(period);
+        let mut index = 0;
+        while position > 0.0 && position >= pattern[index] {
+            position -= pattern[index];
+            index = (index + 1) % pattern.len();
+        }
+        Dasher {
+            pattern,
+            index,
+            remaining: pattern[index] - position,
+        }
+    }
+
+    fn in_dash(&self) -> bool {
+        self.index.is_multiple_of(2)
+    }
+
+    fn advance(&mut self) {
+        self.index = (self.index + 1) % self.pattern.len();
+        self.remaining = self.pattern[self.index];
+    }
+
+    fn figure(&mut self, polyline: &Polyline, style: &StrokeStyle, dashes: &mut Vec<Dash>) {
+        let mut points = polyline.points.clone();
+        if polyline.closed && points.len() > 1 && points[0] != points[points.len() - 1] {
+            points.push(points[0]);
+        }
+        let (first_cap, last_cap) = if polyline.closed {
+            (style.get_dash_cap(), style.get_dash_cap())
+        } else {
+            (style.get_start_cap(), style.get_end_cap())
+        };
+        if points.len() < 2 {
+            if self.in_dash() && !polyline.closed {
+                let direction = Vector2F::new(1.0, 0.0);
+                dashes.push(Dash { points, direction, start_cap: first_cap, end_cap: last_cap });
+            }
+            return;
+        }
+        let start_dash = |point: Point2F, direction: Vector2F, start_cap: CapStyle| Dash {
+            points: vec![point],
+            direction,
+            start_cap,
+            end_cap: style.get_dash_cap(),
+        };
+        let mut current = if self.in_dash() {
+            Some(start_dash(points[0], (points[1] - points[0]).unit(), first_cap))
+        } else {
+            None
+        };
+        let last = points.len() - 2;
+        for (i, pair) in points.windows(2).enumerate() {
+            let (from, to) = (pair[0], pair[1]);
+            let length = (to - from).length();
+            let direction = (to - from).unit();
+            let mut travelled = 0.0;
+            // Whatever ends exactly at the end of the figure is finished below, so
+            // the dash there still gets the end cap.
+            while self.remaining < length - travelled || (i != last && self.remaining == length - travelled) {
+                travelled += self.remaining;
+                let point = from + direction * travelled;
+                match current.take() {
+                    Some(mut dash) => {
+                        push(&mut dash.points, point);
+                        dashes.push(dash);
+                    }
+                    None => current = Some(start_dash(point, direction, style.get_dash_cap())),
+                }
+                self.advance();
+            }
+            self.remaining -= length - travelled;
+            if let Some(ref mut dash) = current {
+                push(&mut dash.points, to);
+            }
+        }
+        if let Some(mut dash) = current {
+            dash.end_cap = last_cap;
+            dashes.push(dash);
+        }
+        if self.remaining == 0.0 {
+            self.advance();
+        }
+    }
+}
+
+fn push(points: &mut Vec<Point2F>, point: Point2F) {
+    if points.last() != Some(&point) {
+        points.push(point);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enums::DashStyle;
+    use geometry::flatten::DEFAULT_FLATTENING_TOLERANCE as TOLERANCE;
+
+    fn line(points: &[(f32, f32)], closed: bool) -> Polyline {
+        let mut polyline = Polyline::new(points[0].into(), closed, false);
+        for &point in &points[1..] {
+            polyline.push(point.into());
+        }
+        polyline
+    }
+
+    fn lengths(dashes: &[Dash]) -> Vec<f32> {
+        let length = |points: &[Point2F]| points.windows(2).map(|pair| (pair[1] - pair[0]).length()).sum();
+        dashes.iter().map(|dash| length(&dash.points)).collect()
+    }
+
+    #[test]
+    fn pattern_continues_across_segments_and_figures() {
+        let style = StrokeStyle::create().with_dash_style(DashStyle::Dash).build();
+        // Dashes of 4 and gaps of 4 with a width of 2, around a corner and into the
+        // next figure.
+        let figures = [line(&[(0.0, 0.0), (6.0, 0.0), (6.0, 6.0)], false), line(&[(0.0, 10.0), (6.0, 10.0)], false)];
+        let dashes = dashes(&figures, 2.0, &style).unwrap();
+        assert_eq!(lengths(&dashes), vec![4.0, 4.0, 2.0]);
+        assert_eq!(dashes[0].points, vec![(0.0, 0.0).into(), (4.0, 0.0).into()]);
+        assert_eq!(dashes[1].points, vec![(6.0, 2.0).into(), (6.0, 6.0).into()]);
+        assert_eq!(dashes[2].points, vec![(4.0, 10.0).into(), (6.0, 10.0).into()]);
+    }
+
+    #[test]
+    fn offset_and_caps() {
+        let style = StrokeStyle::create()
+            .with_dashes(&[1.0, 1.0])
+            .with_dash_offset(0.5)
+            .with_start_cap(CapStyle::Square)
+            .with_end_cap(CapStyle::Triangle)
+            .with_dash_cap(CapStyle::Round)
+            .build();
+        let dashes = dashes(&[line(&[(0.0, 0.0), (5.0, 0.0)], false)], 2.0, &style).unwrap();
+        assert_eq!(lengths(&dashes), vec![1.0, 2.0]);
+        assert_eq!((dashes[0].start_cap, dashes[0].end_cap), (CapStyle::Square, CapStyle::Round));
+        assert_eq!((dashes[1].start_cap, dashes[1].end_cap), (CapStyle::Round, CapStyle::Triangle));
+    }
+
+    #[test]
+    fn dots_have_zero_length() {
+        let style = StrokeStyle::create().with_dash_style(DashStyle::Dot).build();
+        let square = line(&[(0.0, 0.0), (8.0, 0.0), (8.0, 8.0), (0.0, 8.0)], true);
+        let dashes = dashes(&[square], 1.0, &style).unwrap();
+        assert_eq!(dashes.len(), 16);
+        assert!(dashes.iter().all(|dash| dash.points.len() == 1));
+        assert_eq!(dashes[5].direction, Vector2F::new(0.0, 1.0));
+        let solid = StrokeStyle::default();
+        assert_eq!(super::dashes(&[line(&[(0.0, 0.0), (1.0, 0.0)], false)], 1.0, &solid), None);
+    }
+}
@@ -0,0 +1,23 @@
+use geometry::{Geometry, GeometryKind};
+use math;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ellipse {
+    ellipse: math::Ellipse,
+}
+
+impl Ellipse {
+    pub fn create(ellipse: &math::Ellipse) -> Ellipse {
+        Ellipse { ellipse: *ellipse }
+    }
+
+    pub fn get_ellipse(&self) -> math::Ellipse {
+        self.ellipse
+    }
+}
+
+impl Geometry for Ellipse {
+    fn kind(&self) -> GeometryKind<'_> {
+        GeometryKind::Ellipse(&self.ellipse)
+    }
+}
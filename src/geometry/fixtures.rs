@@ -0,0 +1,37 @@
+//! Shapes and measurements the geometry tests share.
+
+use enums::{FigureBegin, FigureEnd, FillMode};
+use geometry::flatten::DEFAULT_FLATTENING_TOLERANCE as TOLERANCE;
+use geometry::Path;
+use math::*;
+
+/// A square from 0 to 100 with a square hole `inset` in from each side, both running
+/// clockwise, so the hole is only empty under the alternate rule.
+pub fn nested_squares(fill_mode: FillMode, inset: f32) -> Path {
+    let (near, far) = (inset, 100.0 - inset);
+    let mut path = Path::new();
+    path.open()
+        .fill_mode(fill_mode)
+        .begin_figure((0.0, 0.0), FigureBegin::Filled, FigureEnd::Closed)
+        .add_lines(&[(100.0, 0.0).into(), (100.0, 100.0).into(), (0.0, 100.0).into()])
+        .end()
+        .begin_figure((near, near), FigureBegin::Filled, FigureEnd::Closed)
+        .add_lines(&[(far, near).into(), (far, far).into(), (near, far).into()])
+        .end();
+    path
+}
+
+/// Signed area of the path's figures, positive for clockwise ones on screen.
+pub fn area(path: &Path) -> f32 {
+    path.figures().iter().map(|figure| figure.signed_area(TOLERANCE)).sum()
+}
+
+/// Area the triangles cover, which all have to run clockwise.
+pub fn triangle_area(triangles: &[Triangle]) -> f32 {
+    let signed = |t: &Triangle| {
+        let (a, b) = (t.point2 - t.point1, t.point3 - t.point1);
+        (a.x * b.y - a.y * b.x) / 2.0
+    };
+    assert!(triangles.iter().all(|triangle| signed(triangle) > 0.0));
+    triangles.iter().map(signed).sum()
+}
@@ -0,0 +1,336 @@
+//! Turning geometries into polylines, like `ID2D1Geometry::Simplify` with
+//! `D2D1_GEOMETRY_SIMPLIFICATION_OPTION_LINES`.
+//!
+//! Curves are split adaptively: a piece is replaced by its chord once its control
+//! points are close enough to the chord that the curve cannot stray further than the
+//! tolerance, so flat stretches take few points and tight bends take many.
+
+use enums::{FigureBegin, FigureEnd};
+use geometry::arc::{self, CenterArc};
+use geometry::path::{Figure, Segment};
+use geometry::{Geometry, GeometryKind, Path};
+use math::*;
+
+use std::f32::consts::PI;
+
+/// Default maximum distance between a curve and its polyline, the same as
+/// `D2D1_DEFAULT_FLATTENING_TOLERANCE`.
+pub const DEFAULT_FLATTENING_TOLERANCE: f32 = 0.25;
+
+/// Deepest subdivision, which bounds the output of degenerate or huge curves.
+const MAX_DEPTH: u32 = 16;
+
+/// A figure flattened to straight lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polyline {
+    pub points: Vec<Point2F>,
+    pub closed: bool,
+    pub filled: bool,
+}
+
+impl Polyline {
+    pub fn new(start: Point2F, closed: bool, filled: bool) -> Polyline {
+        Polyline {
+            points: vec![start],
+            closed,
+            filled,
+        }
+    }
+
+    /// Appends `point` unless it repeats the last one.
+    pub fn push(&mut self, point: Point2F) {
+        if self.points.last() != Some(&point) {
+            self.points.push(point);
+        }
+    }
+}
+
+/// Flattens every figure of the geometry within `tolerance`.
+pub fn flatten(geometry: &dyn Geometry, tolerance: f32) -> Vec<Polyline> {
+    match geometry.kind() {
+        GeometryKind::Rectangle(rect) => vec![rectangle(rect)],
+        GeometryKind::RoundedRectangle(rect) => vec![rounded_rectangle(rect, tolerance)],
+        GeometryKind::Ellipse(ellipse) => vec![ellipse_outline(ellipse, tolerance)],
+        GeometryKind::Path(path) => path_outline(path, tolerance),
+        GeometryKind::Group(group) => {
+            group.get_source_geometries().iter().flat_map(|source| path_outline(source, tolerance)).collect()
+        }
+        GeometryKind::Transformed(transformed) => {
+            let source = transformed.get_source_geometry();
+            path_outline(&source.transformed(&transformed.get_transform()), tolerance)
+        }
+    }
+}
+
+/// Flattens the figures of the geometry as drawn with `transform`. Curves are
+/// transformed before flattening, so the tolerance holds on the transformed figures.
+pub fn flatten_transformed(geometry: &dyn Geometry, transform: Option<&Matrix3x2F>, tolerance: f32) -> Vec<Polyline> {
+    match transform {
+        Some(matrix) => flatten(&Path::from_geometry(geometry).transformed(matrix), tolerance),
+        None => flatten(geometry, tolerance),
+    }
+}
+
+/// The tolerance to flatten with before applying `transform`, for figures that cannot
+/// be transformed first, such as strokes whose pen turns with them. A transform
+/// stretches distances by at most its largest scale factor, the larger singular
+/// value of its linear part.
+pub fn untransformed_tolerance(tolerance: f32, transform: Option<&Matrix3x2F>) -> f32 {
+    let [[a, b], [c, d], _] = match transform {
+        Some(matrix) => matrix.matrix,
+        None => return tolerance,
+    };
+    let (p, q, r) = (a * a + b * b, a * c + b * d, c * c + d * d);
+    let scale = ((p + r) / 2.0 + (((p - r) / 2.0).powi(2) + q * q).sqrt()).sqrt();
+    if scale > 0.0 && scale.is_finite() {
+        tolerance / scale
+    } else {
+        tolerance
+    }
+}
+
+/// Appends the points of the cubic bezier from `from`, excluding `from` itself.
+pub fn flatten_cubic(points: &mut Vec<Point2F>, from: Point2F, bezier: &BezierSegment, tolerance: f32) {
+    subdivide_cubic(points, [from, bezier.point1, bezier.point2, bezier.point3], tolerance, 0);
+}
+
+/// Appends the points of the quadratic bezier from `from`, excluding `from` itself.
+pub fn flatten_quad(points: &mut Vec<Point2F>, from: Point2F, bezier: &QuadBezierSegment, tolerance: f32) {
+    subdivide_quad(points, [from, bezier.point1, bezier.point2], tolerance, 0);
+}
+
+fn subdivide_cubic(points: &mut Vec<Point2F>, p: [Point2F; 4], tolerance: f32, depth: u32) {
+    // The curve stays within 3/4 of the control points' distance from the chord.
+    let flatness = 0.75 * distance_to_chord(p[1], p[0], p[3]).max(distance_to_chord(p[2], p[0], p[3]));
+    if flatness <= tolerance || depth >= MAX_DEPTH {
+        points.push(p[3]);
+        return;
+    }
+    let p01 = midpoint(p[0], p[1]);
+    let p12 = midpoint(p[1], p[2]);
+    let p23 = midpoint(p[2], p[3]);
+    let p012 = midpoint(p01, p12);
+    let p123 = midpoint(p12, p23);
+    let middle = midpoint(p012, p123);
+    subdivide_cubic(points, [p[0], p01, p012, middle], tolerance, depth + 1);
+    subdivide_cubic(points, [middle, p123, p23, p[3]], tolerance, depth + 1);
+}
+
+fn subdivide_quad(points: &mut Vec<Point2F>, p: [Point2F; 3], tolerance: f32, depth: u32) {
+    // The curve stays within half the control point's distance from the chord.
+    let flatness = 0.5 * distance_to_chord(p[1], p[0], p[2]);
+    if flatness <= tolerance || depth >= MAX_DEPTH {
+        points.push(p[2]);
+        return;
+    }
+    let p01 = midpoint(p[0], p[1]);
+    let p12 = midpoint(p[1], p[2]);
+    let middle = midpoint(p01, p12);
+    subdivide_quad(points, [p[0], p01, middle], tolerance, depth + 1);
+    subdivide_quad(points, [middle, p12, p[2]], tolerance, depth + 1);
+}
+
+fn midpoint(a: Point2F, b: Point2F) -> Point2F {
+    Point2F::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
+}
+
+/// Distance from `point` to the segment from `a` to `b`.
+pub fn distance_to_chord(point: Point2F, a: Point2F, b: Point2F) -> f32 {
+    let (chord, offset) = (b - a, point - a);
+    let length = chord.x * chord.x + chord.y * chord.y;
+    if length == 0.0 {
+        return offset.length();
+    }
+    let t = ((offset.x * chord.x + offset.y * chord.y) / length).clamp(0.0, 1.0);
+    (offset - chord * t).length()
+}
+
+fn rectangle(rect: &RectF) -> Polyline {
+    let mut polyline = Polyline::new(Point2F::new(rect.left, rect.top), true, true);
+    polyline.push(Point2F::new(rect.right, rect.top));
+    polyline.push(Point2F::new(rect.right, rect.bottom));
+    polyline.push(Point2F::new(rect.left, rect.bottom));
+    polyline
+}
+
+fn rounded_rectangle(rounded: &RoundedRect, tolerance: f32) -> Polyline {
+    let rect = rounded.rect;
+    let rx = rounded.radius_x.abs().min(rect.width().abs() / 2.0);
+    let ry = rounded.radius_y.abs().min(rect.height().abs() / 2.0);
+    if rx == 0.0 || ry == 0.0 {
+        return rectangle(&rect);
+    }
+    let corners = [
+        (Point2F::new(rect.right - rx, rect.top + ry), -PI / 2.0),
+        (Point2F::new(rect.right - rx, rect.bottom - ry), 0.0),
+        (Point2F::new(rect.left + rx, rect.bottom - ry), PI / 2.0),
+        (Point2F::new(rect.left + rx, rect.top + ry), PI),
+    ];
+    let mut polyline = Polyline::new(Point2F::new(rect.left + rx, rect.top), true, true);
+    for &(center, start_angle) in &corners {
+        let corner = CenterArc {
+            center,
+            radius_x: rx,
+            radius_y: ry,
+            rotation: 0.0,
+            start_angle,
+            sweep_angle: PI / 2.0,
+        };
+        polyline.push(corner.point_at(start_angle));
+        add_center_arc(&mut polyline, &corner, tolerance);
+    }
+    polyline
+}
+
+fn ellipse_outline(ellipse: &Ellipse, tolerance: f32) -> Polyline {
+    let arc = CenterArc {
+        center: ellipse.center,
+        radius_x: ellipse.radius_x.abs(),
+        radius_y: ellipse.radius_y.abs(),
+        rotation: 0.0,
+        start_angle: 0.0,
+        sweep_angle: 2.0 * PI,
+    };
+    let mut polyline = Polyline::new(arc.point_at(0.0), true, true);
+    add_center_arc(&mut polyline, &arc, tolerance);
+    // The last point repeats the first one of the closed figure.
+    if polyline.points.len() > 1 {
+        polyline.points.pop();
+    }
+    polyline
+}
+
+fn path_outline(path: &Path, tolerance: f32) -> Vec<Polyline> {
+    path.figures().iter().map(|figure| flatten_figure(figure, tolerance)).collect()
+}
+
+/// Flattens one figure of a path within `tolerance`.
+pub fn flatten_figure(figure: &Figure, tolerance: f32) -> Polyline {
+    let mut polyline = Polyline::new(figure.start, figure.is_closed(), figure.is_filled());
+    for (from, segment) in figure.iter() {
+        add_segment(&mut polyline, from, segment, tolerance);
+    }
+    polyline
+}
+
+fn add_segment(polyline: &mut Polyline, from: Point2F, segment: &Segment, tolerance: f32) {
+    let mut points = Vec::new();
+    match *segment {
+        Segment::Line(point) => points.push(point),
+        Segment::Bezier(ref bezier) => flatten_cubic(&mut points, from, bezier, tolerance),
+        Segment::QuadBezier(ref bezier) => flatten_quad(&mut points, from, bezier, tolerance),
+        Segment::Arc(ref segment) => {
+            // Half the tolerance goes to the bezier approximation, half to flattening it.
+            let mut start = from;
+            for bezier in arc::to_beziers(from, segment, tolerance / 2.0) {
+                flatten_cubic(&mut points, start, &bezier, tolerance / 2.0);
+                start = bezier.point3;
+            }
+        }
+    }
+    for point in points {
+        polyline.push(point);
+    }
+}
+
+fn add_center_arc(polyline: &mut Polyline, arc: &CenterArc, tolerance: f32) {
+    let mut points = Vec::new();
+    let mut start = arc.point_at(arc.start_angle);
+    for bezier in arc.to_beziers(tolerance / 2.0) {
+        flatten_cubic(&mut points, start, &bezier, tolerance / 2.0);
+        start = bezier.point3;
+    }
+    for point in points {
+        polyline.push(point);
+    }
+}
+
+/// A path with the figures of `geometry` flattened to lines.
+pub fn simplify(geometry: &dyn Geometry, tolerance: f32) -> Path {
+    let mut path = Path::new();
+    {
+        let mut builder = path.open().fill_mode(geometry.get_fill_mode());
+        for polyline in flatten(geometry, tolerance) {
+            let begin = if polyline.filled { FigureBegin::Filled } else { FigureBegin::Hollow };
+            let end = if polyline.closed { FigureEnd::Closed } else { FigureEnd::Open };
+            builder = builder.begin_figure(polyline.points[0], begin, end).add_lines(&polyline.points[1..]).end();
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geometry::bounds::cubic_at;
+
+    /// Largest distance from points of the exact curve to the polyline.
+    fn deviation<F: Fn(f32) -> Point2F>(curve: F, polyline: &[Point2F]) -> f32 {
+        (0..=1000)
+            .map(|i| curve(i as f32 / 1000.0))
+            .map(|p| {
+                polyline
+                    .windows(2)
+                    .map(|w| distance_to_chord(p, w[0], w[1]))
+                    .fold(f32::MAX, f32::min)
+            })
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn cubic_stays_within_tolerance() {
+        let p = [
+            Point2F::new(100.0, 600.0),
+            Point2F::new(50.0, 50.0),
+            Point2F::new(600.0, 50.0),
+            Point2F::new(900.0, 600.0),
+        ];
+        for &tolerance in &[2.0, 0.25, 0.01] {
+            let mut points = vec![p[0]];
+            flatten_cubic(&mut points, p[0], &BezierSegment::new(p[1], p[2], p[3]), tolerance);
+            assert_eq!(points.last(), Some(&p[3]));
+            assert!(deviation(|t| cubic_at(&p, t), &points) <= tolerance);
+        }
+    }
+
+    #[test]
+    fn quad_stays_within_tolerance() {
+        let p = [Point2F::new(100.0, 600.0), Point2F::new(400.0, 0.0), Point2F::new(400.0, 300.0)];
+        let quad = |t: f32| {
+            let mt = 1.0 - t;
+            Point2F::new(
+                mt * mt * p[0].x + 2.0 * mt * t * p[1].x + t * t * p[2].x,
+                mt * mt * p[0].y + 2.0 * mt * t * p[1].y + t * t * p[2].y,
+            )
+        };
+        for &tolerance in &[2.0, 0.25, 0.01] {
+            let mut points = vec![p[0]];
+            flatten_quad(&mut points, p[0], &QuadBezierSegment::new(p[1], p[2]), tolerance);
+            assert!(deviation(quad, &points) <= tolerance);
+        }
+    }
+
+    #[test]
+    fn subdivision_adapts_to_curvature() {
+        let flat = BezierSegment::new((100.0, 0.2), (200.0, -0.2), (300.0, 0.0));
+        let bent = BezierSegment::new((300.0, 0.0), (300.0, 300.0), (0.0, 300.0));
+        let (mut flat_points, mut bent_points) = (Vec::new(), Vec::new());
+        flatten_cubic(&mut flat_points, Point2F::origin(), &flat, DEFAULT_FLATTENING_TOLERANCE);
+        flatten_cubic(&mut bent_points, Point2F::origin(), &bent, DEFAULT_FLATTENING_TOLERANCE);
+        assert_eq!(flat_points.len(), 1);
+        assert!(bent_points.len() > 8);
+    }
+
+    #[test]
+    fn ellipse_stays_within_tolerance() {
+        let ellipse = Ellipse::new((100.0, 100.0), 80.0, 40.0);
+        let polyline = &flatten(&::geometry::Ellipse::create(&ellipse), 0.1)[0];
+        let mut points = polyline.points.clone();
+        points.push(points[0]);
+        let exact = |t: f32| {
+            let angle = 2.0 * PI * t;
+            Point2F::new(100.0 + 80.0 * angle.cos(), 100.0 + 40.0 * angle.sin())
+        };
+        assert!(deviation(exact, &points) <= 0.1);
+    }
+}
@@ -0,0 +1,39 @@
+use enums::FillMode;
+use geometry::{Geometry, GeometryKind, Path};
+
+/// Several geometries filled together with one fill mode, like `ID2D1GeometryGroup`.
+///
+/// The sources are kept as paths, so the fill modes they had of their own are replaced
+/// by the group's.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GeometryGroup {
+    fill_mode: FillMode,
+    geometries: Vec<Path>,
+}
+
+impl GeometryGroup {
+    pub fn create(fill_mode: FillMode, geometries: &[&dyn Geometry]) -> GeometryGroup {
+        GeometryGroup {
+            fill_mode,
+            geometries: geometries.iter().map(|&geometry| Path::from_geometry(geometry)).collect(),
+        }
+    }
+
+    pub fn get_source_geometry_count(&self) -> usize {
+        self.geometries.len()
+    }
+
+    pub fn get_source_geometries(&self) -> &[Path] {
+        &self.geometries
+    }
+}
+
+impl Geometry for GeometryGroup {
+    fn kind(&self) -> GeometryKind<'_> {
+        GeometryKind::Group(self)
+    }
+
+    fn get_fill_mode(&self) -> FillMode {
+        self.fill_mode
+    }
+}
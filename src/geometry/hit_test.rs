@@ -0,0 +1,181 @@
+//! Point containment, like `ID2D1Geometry::FillContainsPoint` and
+//! `StrokeContainsPoint`.
+//!
+//! Geometries are flattened within the caller's tolerance first. The stroke test
+//! checks the same polygons a stroke is drawn with, see `widen::outline`.
+
+use enums::FillMode;
+use geometry::flatten;
+use geometry::widen;
+use geometry::Geometry;
+use math::*;
+use stroke_style::StrokeStyle;
+
+/// Whether the fill of the geometry, drawn with `transform`, covers `point`, following
+/// its fill mode. Hollow figures are ignored and open ones are closed, as when filling.
+pub fn fill_contains_point(geometry: &dyn Geometry, point: Point2F, transform: Option<&Matrix3x2F>, tolerance: f32) -> bool {
+    let polylines = flatten::flatten_transformed(geometry, transform, tolerance);
+    let filled = polylines.iter().filter(|polyline| polyline.filled);
+    let winding: i32 = filled.map(|polyline| winding_number(&polyline.points, point)).sum();
+    match geometry.get_fill_mode() {
+        FillMode::Alternate => winding % 2 != 0,
+        FillMode::Winding => winding != 0,
+    }
+}
+
+/// Whether the stroke of the geometry, drawn with `transform`, covers `point`; gaps
+/// between dashes do not. Points missing the stroke by less than `tolerance` count as
+/// covered, as in Direct2D, which also keeps those on the seams between its pieces.
+pub fn stroke_contains_point(
+    geometry: &dyn Geometry,
+    point: Point2F,
+    stroke_width: f32,
+    stroke_style: Option<&StrokeStyle>,
+    transform: Option<&Matrix3x2F>,
+    tolerance: f32,
+) -> bool {
+    // The pen is transformed along with the geometry, so test in geometry space,
+    // flattening finely enough for the tolerance to hold once transformed.
+    let point = match transform.map(Matrix3x2F::invert) {
+        Some(Some(inverse)) => point * inverse,
+        Some(None) => return false,
+        None => point,
+    };
+
+    let tolerance = flatten::untransformed_tolerance(tolerance, transform);
+    let default_style = StrokeStyle::default();
+    let style = stroke_style.unwrap_or(&default_style);
+    let polylines = flatten::flatten(geometry, tolerance);
+    let polygons = widen::outline(&polylines, stroke_width, style, tolerance);
+    polygons.iter().any(|polygon| {
+        let near = |(i, &p0): (usize, &Point2F)| flatten::distance_to_chord(point, p0, polygon[(i + 1) % polygon.len()]) <= tolerance;
+        winding_number(polygon, point) != 0 || polygon.iter().enumerate().any(near)
+    })
+}
+
+/// Number of times the closed polygon winds around `point`; the sign follows the
+/// direction the polygon is traced in.
+pub fn winding_number(polygon: &[Point2F], point: Point2F) -> i32 {
+    let mut winding = 0;
+    for (i, &p0) in polygon.iter().enumerate() {
+        let p1 = polygon[(i + 1) % polygon.len()];
+        let side = (p1.x - p0.x) * (point.y - p0.y) - (point.x - p0.x) * (p1.y - p0.y);
+        if p0.y <= point.y && p1.y > point.y && side > 0.0 {
+            winding += 1;
+        } else if p1.y <= point.y && p0.y > point.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enums::{CapStyle, FigureBegin, FigureEnd, LineJoin};
+    use geometry::fixtures::nested_squares;
+    use geometry::flatten::DEFAULT_FLATTENING_TOLERANCE as TOLERANCE;
+    use geometry::{Ellipse, GeometryGroup, Path, Rectangle};
+
+    #[test]
+    fn fill_follows_fill_mode() {
+        let (alternate, winding) = (nested_squares(FillMode::Alternate, 25.0), nested_squares(FillMode::Winding, 25.0));
+        assert!(fill_contains_point(&alternate, Point2F::new(10.0, 50.0), None, TOLERANCE));
+        assert!(!fill_contains_point(&alternate, Point2F::new(50.0, 50.0), None, TOLERANCE));
+        assert!(fill_contains_point(&winding, Point2F::new(50.0, 50.0), None, TOLERANCE));
+        assert!(!fill_contains_point(&winding, Point2F::new(150.0, 50.0), None, TOLERANCE));
+    }
+
+    #[test]
+    fn figures_report_area_and_winding() {
+        let mut path = Path::new();
+        path.open()
+            .begin_figure((0.0, 0.0), FigureBegin::Filled, FigureEnd::Closed)
+            .add_lines(&[(100.0, 0.0).into(), (100.0, 50.0).into(), (0.0, 50.0).into()])
+            .end()
+            // A bow tie: its loops cancel out and wind opposite ways.
+            .begin_figure((0.0, 0.0), FigureBegin::Filled, FigureEnd::Open)
+            .add_lines(&[(100.0, 100.0).into(), (100.0, 0.0).into(), (0.0, 100.0).into()])
+            .end();
+        let (square, bow_tie) = (&path.figures()[0], &path.figures()[1]);
+        assert_eq!(square.signed_area(TOLERANCE), 5000.0);
+        assert_eq!(square.winding_number(Point2F::new(50.0, 25.0), TOLERANCE), 1);
+        assert_eq!(square.winding_number(Point2F::new(50.0, 75.0), TOLERANCE), 0);
+        assert_eq!(bow_tie.signed_area(TOLERANCE), 0.0);
+        assert_eq!(bow_tie.winding_number(Point2F::new(90.0, 50.0), TOLERANCE), -1);
+        assert_eq!(bow_tie.winding_number(Point2F::new(10.0, 50.0), TOLERANCE), 1);
+    }
+
+    #[test]
+    fn fill_honors_transform() {
+        let ellipse = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 10.0, 10.0));
+        let shift = Matrix3x2F::translation((100.0, 0.0));
+        assert!(fill_contains_point(&ellipse, Point2F::new(105.0, 0.0), Some(&shift), TOLERANCE));
+        assert!(!fill_contains_point(&ellipse, Point2F::new(5.0, 0.0), Some(&shift), TOLERANCE));
+    }
+
+    #[test]
+    fn groups_and_transformed_geometries() {
+        // Two squares overlapping in the middle, filled with the group's mode.
+        let a = Rectangle::create(&RectF::new(0.0, 0.0, 100.0, 100.0));
+        let b = Rectangle::create(&RectF::new(50.0, 50.0, 150.0, 150.0));
+        let overlap = Point2F::new(75.0, 75.0);
+        let alternate = GeometryGroup::create(FillMode::Alternate, &[&a, &b]);
+        let winding = GeometryGroup::create(FillMode::Winding, &[&a, &b]);
+        assert!(fill_contains_point(&alternate, Point2F::new(25.0, 25.0), None, TOLERANCE));
+        assert!(!fill_contains_point(&alternate, overlap, None, TOLERANCE));
+        assert!(fill_contains_point(&winding, overlap, None, TOLERANCE));
+        // Every source is stroked, including the sides inside the other.
+        assert!(stroke_contains_point(&winding, Point2F::new(52.0, 75.0), 10.0, None, None, TOLERANCE));
+        assert!(stroke_contains_point(&winding, Point2F::new(102.0, 75.0), 10.0, None, None, TOLERANCE));
+        assert!(!stroke_contains_point(&winding, overlap, 10.0, None, None, TOLERANCE));
+
+        // A small square scaled up: its own transform moves the outline but leaves the
+        // stroke width alone, and the one it is drawn with applies after it.
+        let small = Rectangle::create(&RectF::new(0.0, 0.0, 10.0, 10.0));
+        let scaled = small.transformed(&Matrix3x2F::scale((10.0, 10.0), (0.0, 0.0)));
+        let shift = Matrix3x2F::translation((100.0, 0.0));
+        assert!(fill_contains_point(&scaled, Point2F::new(90.0, 90.0), None, TOLERANCE));
+        assert!(!fill_contains_point(&scaled, Point2F::new(90.0, 90.0), Some(&shift), TOLERANCE));
+        assert!(fill_contains_point(&scaled, Point2F::new(190.0, 90.0), Some(&shift), TOLERANCE));
+        assert!(stroke_contains_point(&scaled, Point2F::new(50.0, 4.0), 10.0, None, None, TOLERANCE));
+        assert!(!stroke_contains_point(&scaled, Point2F::new(50.0, 6.0), 10.0, None, None, TOLERANCE));
+        assert!(stroke_contains_point(&scaled, Point2F::new(196.0, 50.0), 10.0, None, Some(&shift), TOLERANCE));
+        assert!(!stroke_contains_point(&scaled, Point2F::new(94.0, 50.0), 10.0, None, Some(&shift), TOLERANCE));
+    }
+
+    #[test]
+    fn stroke_honors_joins_and_caps() {
+        let rect = Rectangle::create(&RectF::new(0.0, 0.0, 100.0, 100.0));
+        // Inside the band, outside of it, and in the corner a miter fills.
+        assert!(stroke_contains_point(&rect, Point2F::new(50.0, 4.0), 10.0, None, None, TOLERANCE));
+        assert!(!stroke_contains_point(&rect, Point2F::new(50.0, 6.0), 10.0, None, None, TOLERANCE));
+        assert!(stroke_contains_point(&rect, Point2F::new(-4.0, -4.0), 10.0, None, None, TOLERANCE));
+        let round = StrokeStyle::create().with_line_join(LineJoin::Round).build();
+        assert!(!stroke_contains_point(&rect, Point2F::new(-4.0, -4.0), 10.0, Some(&round), None, TOLERANCE));
+
+        let mut line = Path::new();
+        line.open().begin_figure((0.0, 0.0), FigureBegin::Hollow, FigureEnd::Open).add_line((100.0, 0.0)).end();
+        let square = StrokeStyle::create().with_start_cap(CapStyle::Square).build();
+        assert!(!stroke_contains_point(&line, Point2F::new(-3.0, 0.0), 10.0, None, None, TOLERANCE));
+        assert!(stroke_contains_point(&line, Point2F::new(-3.0, 0.0), 10.0, Some(&square), None, TOLERANCE));
+    }
+
+    #[test]
+    fn stroke_tolerance_holds_after_transform() {
+        // A unit circle drawn a hundred times larger, with a stroke 2 wide: flattened
+        // within the tolerance in its own space, chords would cut through the stroke
+        // and points a hundred times the tolerance away would count as covered.
+        let circle = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 1.0, 1.0));
+        let zoom = Matrix3x2F::scale((100.0, 100.0), (0.0, 0.0));
+        let contains = |radius: f32, degrees: f32| {
+            let (sin, cos) = degrees.to_radians().sin_cos();
+            stroke_contains_point(&circle, Point2F::new(radius * cos, radius * sin), 0.02, None, Some(&zoom), TOLERANCE)
+        };
+        for i in 0..360 {
+            let degrees = i as f32;
+            assert!(contains(100.0, degrees) && contains(100.9, degrees) && contains(99.1, degrees), "{}", degrees);
+            assert!(!contains(101.5, degrees) && !contains(98.5, degrees), "{}", degrees);
+        }
+    }
+}
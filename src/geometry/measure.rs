@@ -0,0 +1,104 @@
+//! Distances along geometries, like `ID2D1Geometry::ComputeLength` and
+//! `ComputePointAtLength`.
+//!
+//! Geometries are flattened within the tolerance and measured along the lines, so
+//! curves are only as accurate as the tolerance. Figures follow each other in drawing
+//! order and closed figures include their closing side, as when they are stroked.
+
+use geometry::flatten::{self, Polyline};
+use geometry::Geometry;
+use math::*;
+
+/// Length of all the figures of the geometry drawn with `transform`.
+pub fn compute_length(geometry: &dyn Geometry, transform: Option<&Matrix3x2F>, tolerance: f32) -> f32 {
+    flatten::flatten_transformed(geometry, transform, tolerance)
+        .iter()
+        .flat_map(sides)
+        .map(|(from, to)| (to - from).length())
+        .sum()
+}
+
+/// The point `length` along the geometry drawn with `transform`, and the unit tangent
+/// there. Lengths before the start or past the end are clamped to the ends; a geometry
+/// without any length gives its first point, or the origin, and a zero tangent.
+pub fn compute_point_at_length(
+    geometry: &dyn Geometry,
+    length: f32,
+    transform: Option<&Matrix3x2F>,
+    tolerance: f32,
+) -> (Point2F, Vector2F) {
+    let polylines = flatten::flatten_transformed(geometry, transform, tolerance);
+    let mut last = None;
+    let mut remaining = length.max(0.0);
+    for (from, to) in polylines.iter().flat_map(sides) {
+        let side = (to - from).length();
+        if side == 0.0 {
+            continue;
+        }
+        let tangent = (to - from) / side;
+        if remaining <= side {
+            return (from + tangent * remaining, tangent);
+        }
+        remaining -= side;
+        last = Some((to, tangent));
+    }
+    last.unwrap_or_else(|| {
+        let start = polylines.first().map_or(Point2F::origin(), |polyline| polyline.points[0]);
+        (start, Vector2F::zero())
+    })
+}
+
+/// The straight sides of a flattened figure, with the closing one.
+fn sides(polyline: &Polyline) -> Vec<(Point2F, Point2F)> {
+    let points = &polyline.points;
+    let mut sides: Vec<_> = points.windows(2).map(|pair| (pair[0], pair[1])).collect();
+    if polyline.closed && points.len() > 1 {
+        sides.push((points[points.len() - 1], points[0]));
+    }
+    sides
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enums::{FigureBegin, FigureEnd};
+    use geometry::flatten::DEFAULT_FLATTENING_TOLERANCE as TOLERANCE;
+    use geometry::{Ellipse, Path, Rectangle};
+
+    #[test]
+    fn measures_figures_in_order() {
+        let rect = Rectangle::create(&RectF::new(0.0, 0.0, 100.0, 50.0));
+        assert_eq!(rect.compute_length(None, TOLERANCE), 300.0);
+        // Clockwise from the top left corner, and around the closing side.
+        let at = |length| rect.point_at_length(length, None, TOLERANCE);
+        assert_eq!(at(120.0), (Point2F::new(100.0, 20.0), Vector2F::new(0.0, 1.0)));
+        assert_eq!(at(280.0), (Point2F::new(0.0, 20.0), Vector2F::new(0.0, -1.0)));
+        assert_eq!(at(-5.0), (Point2F::new(0.0, 0.0), Vector2F::new(1.0, 0.0)));
+        assert_eq!(at(500.0), (Point2F::new(0.0, 0.0), Vector2F::new(0.0, -1.0)));
+
+        let mut path = Path::new();
+        path.open()
+            .begin_figure((0.0, 0.0), FigureBegin::Hollow, FigureEnd::Open)
+            .add_line((10.0, 0.0))
+            .end()
+            .begin_figure((0.0, 10.0), FigureBegin::Hollow, FigureEnd::Open)
+            .add_line((0.0, 30.0))
+            .end();
+        assert_eq!(path.compute_length(None, TOLERANCE), 30.0);
+        assert_eq!(path.point_at_length(15.0, None, TOLERANCE), (Point2F::new(0.0, 15.0), Vector2F::new(0.0, 1.0)));
+    }
+
+    #[test]
+    fn measures_curves_with_transform() {
+        let circle = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 100.0, 100.0));
+        let double = Matrix3x2F::scale((2.0, 2.0), (0.0, 0.0));
+        let circumference = 2.0 * ::std::f32::consts::PI * 200.0;
+        let length = circle.compute_length(Some(&double), TOLERANCE);
+        assert!((length - circumference).abs() < circumference * 0.001);
+        // A quarter of the way round from the rightmost point, heading left.
+        let (point, tangent) = circle.point_at_length(length / 4.0, Some(&double), TOLERANCE);
+        assert!((point - Point2F::new(0.0, 200.0)).length() < 1.0);
+        // The tangent is the direction of the line the point falls on.
+        assert!((tangent - Vector2F::new(-1.0, 0.0)).length() < 0.1);
+    }
+}
@@ -0,0 +1,168 @@
+use enums::{CombineMode, FillMode, GeometryRelation};
+use math;
+use stroke_style::StrokeStyle;
+
+#[doc(inline)]
+pub use self::ellipse::Ellipse;
+#[doc(inline)]
+pub use self::group::GeometryGroup;
+#[doc(inline)]
+pub use self::path::Path;
+#[doc(inline)]
+pub use self::rectangle::Rectangle;
+#[doc(inline)]
+pub use self::rounded_rectangle::RoundedRectangle;
+#[doc(inline)]
+pub use self::transformed::TransformedGeometry;
+
+pub mod arc;
+pub mod bounds;
+pub mod combine;
+pub mod compare;
+pub mod dash;
+pub mod ellipse;
+#[cfg(test)]
+mod fixtures;
+pub mod flatten;
+pub mod group;
+pub mod hit_test;
+pub mod measure;
+pub mod path;
+pub mod rectangle;
+pub mod rounded_rectangle;
+pub mod svg_path;
+pub mod tessellate;
+pub mod text;
+pub mod transformed;
+pub mod widen;
+
+/// Borrowed view of a geometry, which is what backends dispatch on.
+#[derive(Copy, Clone, Debug)]
+pub enum GeometryKind<'a> {
+    Rectangle(&'a math::RectF),
+    RoundedRectangle(&'a math::RoundedRect),
+    Ellipse(&'a math::Ellipse),
+    Path(&'a Path),
+    Group(&'a GeometryGroup),
+    Transformed(&'a TransformedGeometry),
+}
+
+/// Device independent shape that any canvas can fill or stroke.
+pub trait Geometry {
+    fn kind(&self) -> GeometryKind<'_>;
+
+    /// The rule deciding which areas of overlapping figures are filled. Shapes have a
+    /// single figure, which both rules fill alike.
+    fn get_fill_mode(&self) -> FillMode {
+        match self.kind() {
+            GeometryKind::Path(path) => path.get_fill_mode(),
+            GeometryKind::Group(group) => group.get_fill_mode(),
+            GeometryKind::Transformed(transformed) => transformed.get_fill_mode(),
+            _ => FillMode::Alternate,
+        }
+    }
+
+    /// Smallest axis-aligned rectangle around the geometry after `transform`.
+    fn bounds(&self, transform: Option<&math::Matrix3x2F>) -> math::RectF {
+        bounds::bounds(&self.kind(), transform)
+    }
+
+    /// Smallest axis-aligned rectangle around the stroke of the geometry after `transform`.
+    fn widened_bounds(&self, stroke_width: f32, stroke_style: Option<&StrokeStyle>, transform: Option<&math::Matrix3x2F>) -> math::RectF {
+        bounds::widened_bounds(&self.kind(), stroke_width, stroke_style, transform)
+    }
+
+    /// Whether the fill of the geometry, drawn with `transform`, covers `point`.
+    fn fill_contains_point(&self, point: math::Point2F, transform: Option<&math::Matrix3x2F>, tolerance: f32) -> bool {
+        hit_test::fill_contains_point(&self.kind(), point, transform, tolerance)
+    }
+
+    /// Whether the stroke of the geometry, drawn with `transform`, covers `point`.
+    fn stroke_contains_point(
+        &self,
+        point: math::Point2F,
+        stroke_width: f32,
+        stroke_style: Option<&StrokeStyle>,
+        transform: Option<&math::Matrix3x2F>,
+        tolerance: f32,
+    ) -> bool {
+        hit_test::stroke_contains_point(&self.kind(), point, stroke_width, stroke_style, transform, tolerance)
+    }
+
+    /// The region covered by the geometry, by `input` drawn with `input_transform`, or
+    /// both, depending on `mode`, as a path of lines within `tolerance`.
+    fn combine_with_geometry(
+        &self,
+        input: &dyn Geometry,
+        mode: CombineMode,
+        input_transform: Option<&math::Matrix3x2F>,
+        tolerance: f32,
+    ) -> Path {
+        combine::combine(&self.kind(), input, mode, input_transform, tolerance)
+    }
+
+    /// Whether the fill of the geometry and that of `input`, drawn with
+    /// `input_transform`, are apart, nested or overlapping, flattened within `tolerance`.
+    fn compare_with_geometry(
+        &self,
+        input: &dyn Geometry,
+        input_transform: Option<&math::Matrix3x2F>,
+        tolerance: f32,
+    ) -> GeometryRelation {
+        compare::compare(&self.kind(), input, input_transform, tolerance)
+    }
+
+    /// Length of the figures of the geometry drawn with `transform`, measured within
+    /// `tolerance`.
+    fn compute_length(&self, transform: Option<&math::Matrix3x2F>, tolerance: f32) -> f32 {
+        measure::compute_length(&self.kind(), transform, tolerance)
+    }
+
+    /// The point `length` along the figures of the geometry drawn with `transform`, with
+    /// the unit tangent there.
+    fn point_at_length(&self, length: f32, transform: Option<&math::Matrix3x2F>, tolerance: f32) -> (math::Point2F, math::Vector2F) {
+        measure::compute_point_at_length(&self.kind(), length, transform, tolerance)
+    }
+
+    /// The dashes the stroke style lays out along the geometry, as open figures of
+    /// lines within `tolerance`.
+    fn dash(&self, stroke_width: f32, stroke_style: Option<&StrokeStyle>, tolerance: f32) -> Path {
+        dash::dash(&self.kind(), stroke_width, stroke_style, tolerance)
+    }
+
+    /// The stroke of the geometry, drawn with `transform`, as a path to fill.
+    fn widen(
+        &self,
+        stroke_width: f32,
+        stroke_style: Option<&StrokeStyle>,
+        transform: Option<&math::Matrix3x2F>,
+        tolerance: f32,
+    ) -> Path {
+        widen::widen(&self.kind(), stroke_width, stroke_style, transform, tolerance)
+    }
+
+    /// Triangles covering the fill of the geometry drawn with `transform`, with curves
+    /// flattened within `tolerance`.
+    fn tessellate(&self, transform: Option<&math::Matrix3x2F>, tolerance: f32) -> Vec<math::Triangle> {
+        tessellate::tessellate(&self.kind(), transform, tolerance)
+    }
+
+    /// The geometry drawn with `transform`. Its outline is copied into a path the
+    /// result keeps, so later changes to this geometry do not show through.
+    fn transformed(&self, transform: &math::Matrix3x2F) -> TransformedGeometry {
+        TransformedGeometry::create(&self.kind(), transform)
+    }
+
+    /// The geometry with every curve flattened to lines within `tolerance`.
+    fn simplify(&self, tolerance: f32) -> Path {
+        flatten::simplify(&self.kind(), tolerance)
+    }
+}
+
+/// A view is a geometry too, which lets trait methods hand `self` to functions
+/// taking `&dyn Geometry`.
+impl<'a> Geometry for GeometryKind<'a> {
+    fn kind(&self) -> GeometryKind<'_> {
+        *self
+    }
+}
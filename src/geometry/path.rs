@@ -0,0 +1,410 @@
+use enums::{ArcSize, FigureBegin, FigureEnd, FillMode, SweepDirection};
+use geometry::flatten;
+use geometry::hit_test;
+use geometry::svg_path;
+use geometry::text;
+use geometry::{Geometry, GeometryKind};
+use math;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Custom-shaped geometry made of lines and curves.
+///
+/// Figures are stored as a start point followed by segments, so any backend can
+/// walk them and they can be transformed without a device.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Path {
+    figures: Vec<Figure>,
+    #[serde(default)]
+    fill_mode: FillMode,
+}
+
+/// A connected run of segments, started with `GeometryBuilder::begin_figure`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Figure {
+    pub start: math::Point2F,
+    pub begin: FigureBegin,
+    pub end: FigureEnd,
+    pub segments: Vec<Segment>,
+}
+
+/// One piece of a figure. It starts where the previous segment, or the figure's
+/// start point, ends.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Segment {
+    Line(math::Point2F),
+    Bezier(math::BezierSegment),
+    QuadBezier(math::QuadBezierSegment),
+    Arc(math::ArcSegment),
+}
+
+impl Path {
+    pub fn new() -> Path {
+        Path::default()
+    }
+
+    /// Starts adding figures. Figures added before are kept.
+    pub fn open(&mut self) -> GeometryBuilder<'_> {
+        GeometryBuilder { path: self }
+    }
+
+    /// The outline of any geometry as a path, with shapes traced clockwise from their
+    /// top left (rectangles) or rightmost (ellipses) point.
+    pub fn from_geometry(geometry: &dyn Geometry) -> Path {
+        let mut path = Path::new();
+        match geometry.kind() {
+            GeometryKind::Rectangle(rect) => {
+                let corners = [(rect.right, rect.top).into(), (rect.right, rect.bottom).into(), (rect.left, rect.bottom).into()];
+                path.open().begin_figure((rect.left, rect.top), FigureBegin::Filled, FigureEnd::Closed).add_lines(&corners).end();
+            }
+            GeometryKind::RoundedRectangle(rounded) => {
+                let rect = rounded.rect;
+                let rx = rounded.radius_x.abs().min(rect.width().abs() / 2.0);
+                let ry = rounded.radius_y.abs().min(rect.height().abs() / 2.0);
+                if rx == 0.0 || ry == 0.0 {
+                    return Path::from_geometry(&GeometryKind::Rectangle(&rect));
+                }
+                let corner = |x: f32, y: f32| math::ArcSegment::new((x, y), (rx, ry), 0.0, SweepDirection::Clockwise, ArcSize::Small);
+                path.open()
+                    .begin_figure((rect.left + rx, rect.top), FigureBegin::Filled, FigureEnd::Closed)
+                    .add_line((rect.right - rx, rect.top))
+                    .add_arc(&corner(rect.right, rect.top + ry))
+                    .add_line((rect.right, rect.bottom - ry))
+                    .add_arc(&corner(rect.right - rx, rect.bottom))
+                    .add_line((rect.left + rx, rect.bottom))
+                    .add_arc(&corner(rect.left, rect.bottom - ry))
+                    .add_line((rect.left, rect.top + ry))
+                    .add_arc(&corner(rect.left + rx, rect.top))
+                    .end();
+            }
+            GeometryKind::Ellipse(ellipse) => {
+                let (center, rx, ry) = (ellipse.center, ellipse.radius_x.abs(), ellipse.radius_y.abs());
+                let half = |x: f32| math::ArcSegment::new((x, center.y), (rx, ry), 0.0, SweepDirection::Clockwise, ArcSize::Small);
+                path.open()
+                    .begin_figure((center.x + rx, center.y), FigureBegin::Filled, FigureEnd::Closed)
+                    .add_arc(&half(center.x - rx))
+                    .add_arc(&half(center.x + rx))
+                    .end();
+            }
+            GeometryKind::Path(other) => path = other.clone(),
+            GeometryKind::Group(group) => {
+                for source in group.get_source_geometries() {
+                    path.figures.extend_from_slice(source.figures());
+                }
+                path.fill_mode = group.get_fill_mode();
+            }
+            GeometryKind::Transformed(transformed) => {
+                path = transformed.get_source_geometry().transformed(&transformed.get_transform());
+            }
+        }
+        path
+    }
+
+    /// Builds a path from the data of an SVG `<path>` element, such as
+    /// `"M100 600 C50 50 600 50 900 600 Z"`.
+    pub fn from_svg(data: &str) -> Result<Path, String> {
+        svg_path::parse(data)
+    }
+
+    pub fn figures(&self) -> &[Figure] {
+        &self.figures
+    }
+
+    pub fn get_figure_count(&self) -> usize {
+        self.figures.len()
+    }
+
+    pub fn get_segment_count(&self) -> usize {
+        self.figures.iter().map(|figure| figure.segments.len()).sum()
+    }
+
+    /// Applies `matrix` to every point of the path.
+    pub fn transform(&mut self, matrix: &math::Matrix3x2F) {
+        for figure in &mut self.figures {
+            figure.transform(matrix);
+        }
+    }
+
+    /// Returns a copy of the path with `matrix` applied.
+    pub fn transformed(&self, matrix: &math::Matrix3x2F) -> Path {
+        let mut path = self.clone();
+        path.transform(matrix);
+        path
+    }
+}
+
+/// The compact text form, which `parse` reads back exactly.
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::write(self, f)
+    }
+}
+
+impl FromStr for Path {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Path, String> {
+        text::parse(text)
+    }
+}
+
+impl Geometry for Path {
+    fn kind(&self) -> GeometryKind<'_> {
+        GeometryKind::Path(self)
+    }
+
+    fn get_fill_mode(&self) -> FillMode {
+        self.fill_mode
+    }
+}
+
+impl Figure {
+    pub fn is_filled(&self) -> bool {
+        self.begin == FigureBegin::Filled
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.end == FigureEnd::Closed
+    }
+
+    /// Where the last segment ends, or the start point of an empty figure.
+    pub fn end_point(&self) -> math::Point2F {
+        self.segments.last().map_or(self.start, Segment::end_point)
+    }
+
+    /// Area the figure encloses once closed, flattened within `tolerance`. It is
+    /// positive for figures running clockwise on screen and negative for the others;
+    /// the loops of a figure that crosses itself count with their own signs.
+    pub fn signed_area(&self, tolerance: f32) -> f32 {
+        let points = flatten::flatten_figure(self, tolerance).points;
+        let mut area = 0.0;
+        for (i, p0) in points.iter().enumerate() {
+            let p1 = points[(i + 1) % points.len()];
+            area += p0.x * p1.y - p1.x * p0.y;
+        }
+        area / 2.0
+    }
+
+    /// How many times the figure, once closed and flattened within `tolerance`, winds
+    /// clockwise around `point`. A figure that crosses itself winds around some points
+    /// more than once, or both ways around different points.
+    pub fn winding_number(&self, point: math::Point2F, tolerance: f32) -> i32 {
+        hit_test::winding_number(&flatten::flatten_figure(self, tolerance).points, point)
+    }
+
+    /// The segments paired with the points they start from.
+    pub fn iter(&self) -> impl Iterator<Item = (math::Point2F, &Segment)> + '_ {
+        let mut from = self.start;
+        self.segments.iter().map(move |segment| {
+            let start = from;
+            from = segment.end_point();
+            (start, segment)
+        })
+    }
+
+    pub fn transform(&mut self, matrix: &math::Matrix3x2F) {
+        self.start = self.start * *matrix;
+        for segment in &mut self.segments {
+            segment.transform(matrix);
+        }
+    }
+}
+
+impl Segment {
+    pub fn end_point(&self) -> math::Point2F {
+        match *self {
+            Segment::Line(point) => point,
+            Segment::Bezier(ref bezier) => bezier.point3,
+            Segment::QuadBezier(ref bezier) => bezier.point2,
+            Segment::Arc(ref arc) => arc.point,
+        }
+    }
+
+    pub fn transform(&mut self, matrix: &math::Matrix3x2F) {
+        let m = *matrix;
+        match *self {
+            Segment::Line(ref mut point) => *point = *point * m,
+            Segment::Bezier(ref mut bezier) => {
+                bezier.point1 = bezier.point1 * m;
+                bezier.point2 = bezier.point2 * m;
+                bezier.point3 = bezier.point3 * m;
+            }
+            Segment::QuadBezier(ref mut bezier) => {
+                bezier.point1 = bezier.point1 * m;
+                bezier.point2 = bezier.point2 * m;
+            }
+            Segment::Arc(ref mut arc) => transform_arc(arc, matrix),
+        }
+    }
+}
+
+/// An affine transform maps an ellipse to another ellipse; its radii and rotation are
+/// the singular values and direction of the transformed ellipse axes. A mirroring
+/// transform also reverses the sweep.
+fn transform_arc(arc: &mut math::ArcSegment, matrix: &math::Matrix3x2F) {
+    let [[a, b], [c, d], _] = matrix.matrix;
+    let (sin, cos) = arc.rotation_angle.to_radians().sin_cos();
+    let axis_x = (arc.size.width * cos, arc.size.width * sin);
+    let axis_y = (-arc.size.height * sin, arc.size.height * cos);
+    let map = |(x, y): (f32, f32)| (x * a + y * c, x * b + y * d);
+    // The transformed axes, which are conjugate diameters of the new ellipse.
+    let (u, v) = (map(axis_x), map(axis_y));
+    // Shape matrix of the transformed ellipse: u u^T + v v^T.
+    let p = u.0 * u.0 + v.0 * v.0;
+    let q = u.0 * u.1 + v.0 * v.1;
+    let r = u.1 * u.1 + v.1 * v.1;
+    let mean = (p + r) / 2.0;
+    let spread = (((p - r) / 2.0).powi(2) + q * q).sqrt();
+    arc.size = math::SizeF::new((mean + spread).max(0.0).sqrt(), (mean - spread).max(0.0).sqrt());
+    arc.rotation_angle = (0.5 * (2.0 * q).atan2(p - r)).to_degrees();
+    if matrix.determinant() < 0.0 {
+        arc.sweep_direction = match arc.sweep_direction {
+            SweepDirection::Clockwise => SweepDirection::CounterClockwise,
+            SweepDirection::CounterClockwise => SweepDirection::Clockwise,
+        };
+    }
+    arc.point = arc.point * *matrix;
+}
+
+/// Interface for building Path geometry
+pub struct GeometryBuilder<'a> {
+    path: &'a mut Path,
+}
+
+impl<'a> GeometryBuilder<'a> {
+    /// Sets the rule deciding which areas of overlapping figures are filled.
+    pub fn fill_mode(self, fill_mode: FillMode) -> Self {
+        self.path.fill_mode = fill_mode;
+        self
+    }
+
+    pub fn begin_figure<P>(self, start: P, begin: FigureBegin, end: FigureEnd) -> FigureBuilder<'a>
+    where
+        P: Into<math::Point2F>,
+    {
+        self.path.figures.push(Figure {
+            start: start.into(),
+            begin,
+            end,
+            segments: Vec::new(),
+        });
+        FigureBuilder { builder: self }
+    }
+}
+
+pub struct FigureBuilder<'a> {
+    builder: GeometryBuilder<'a>,
+}
+
+impl<'a> FigureBuilder<'a> {
+    pub fn end(self) -> GeometryBuilder<'a> {
+        self.builder
+    }
+
+    pub fn add_line<P: Into<math::Point2F>>(self, point: P) -> Self {
+        self.push(Segment::Line(point.into()))
+    }
+
+    pub fn add_lines(self, points: &[math::Point2F]) -> Self {
+        points.iter().fold(self, |figure, point| figure.add_line(*point))
+    }
+
+    pub fn add_bezier(self, bezier: &math::BezierSegment) -> Self {
+        self.push(Segment::Bezier(*bezier))
+    }
+
+    pub fn add_beziers(self, beziers: &[math::BezierSegment]) -> Self {
+        beziers.iter().fold(self, |figure, bezier| figure.add_bezier(bezier))
+    }
+
+    pub fn add_quadratic_bezier(self, bezier: &math::QuadBezierSegment) -> Self {
+        self.push(Segment::QuadBezier(*bezier))
+    }
+
+    pub fn add_quadratic_beziers(self, beziers: &[math::QuadBezierSegment]) -> Self {
+        beziers.iter().fold(self, |figure, bezier| figure.add_quadratic_bezier(bezier))
+    }
+
+    pub fn add_arc(self, arc: &math::ArcSegment) -> Self {
+        self.push(Segment::Arc(*arc))
+    }
+
+    fn push(self, segment: Segment) -> Self {
+        self.builder.path.figures.last_mut().unwrap().segments.push(segment);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geometry::flatten::{distance_to_chord, Polyline};
+    use math::*;
+
+    /// Largest distance from a point of either polyline to the other one.
+    fn distance(a: &Polyline, b: &Polyline) -> f32 {
+        let one_way = |a: &Polyline, b: &Polyline| {
+            a.points
+                .iter()
+                .map(|&p| b.points.windows(2).map(|w| distance_to_chord(p, w[0], w[1])).fold(f32::MAX, f32::min))
+                .fold(0.0, f32::max)
+        };
+        one_way(a, b).max(one_way(b, a))
+    }
+
+    /// Two rotated elliptical arcs, a large and a small one sweeping opposite ways.
+    fn arcs() -> Path {
+        let mut path = Path::new();
+        path.open()
+            .begin_figure((0.0, 0.0), FigureBegin::Filled, FigureEnd::Open)
+            .add_arc(&ArcSegment::new((100.0, 50.0), (80.0, 40.0), 20.0, SweepDirection::Clockwise, ArcSize::Large))
+            .add_arc(&ArcSegment::new((150.0, 0.0), (60.0, 30.0), -45.0, SweepDirection::CounterClockwise, ArcSize::Small))
+            .end();
+        path
+    }
+
+    #[test]
+    fn transforming_arcs_commutes_with_flattening() {
+        let transforms = [
+            Matrix3x2F::rotation(30f32.to_radians(), (50.0, 50.0)),
+            Matrix3x2F::scale((2.0, 0.5), (0.0, 0.0)) * Matrix3x2F::translation((10.0, -20.0)),
+            Matrix3x2F::scale((-1.0, 1.0), (0.0, 0.0)) * Matrix3x2F::rotation(10f32.to_radians(), (0.0, 0.0)),
+            Matrix3x2F::skew(25f32.to_radians(), 0.0, (0.0, 0.0)),
+        ];
+        let tolerance = 0.01;
+        for matrix in &transforms {
+            let mut flattened = flatten::flatten(&arcs(), tolerance).remove(0);
+            for point in &mut flattened.points {
+                *point = *point * *matrix;
+            }
+            let transformed = flatten::flatten(&arcs().transformed(matrix), tolerance).remove(0);
+            assert!(distance(&transformed, &flattened) < 0.1, "{:?}", matrix);
+        }
+    }
+
+    #[test]
+    fn arcs_take_radii_rotation_and_sweep_from_the_transform() {
+        let arc = |path: &Path| match path.figures()[0].segments[0] {
+            Segment::Arc(arc) => arc,
+            ref segment => panic!("expected an arc, found {:?}", segment),
+        };
+        let mut stretched = ArcSegment::new((100.0, 50.0), (80.0, 40.0), 0.0, SweepDirection::Clockwise, ArcSize::Large);
+        transform_arc(&mut stretched, &Matrix3x2F::scale((2.0, 0.5), (0.0, 0.0)));
+        assert_eq!((stretched.size, stretched.rotation_angle), (SizeF::new(160.0, 20.0), 0.0));
+        assert_eq!(stretched.sweep_direction, SweepDirection::Clockwise);
+        // Stretching along the minor axis makes it the major one.
+        let mut turned = ArcSegment::new((100.0, 50.0), (80.0, 40.0), 0.0, SweepDirection::Clockwise, ArcSize::Large);
+        transform_arc(&mut turned, &Matrix3x2F::scale((1.0, 3.0), (0.0, 0.0)));
+        assert_eq!((turned.size, turned.rotation_angle.abs()), (SizeF::new(120.0, 80.0), 90.0));
+
+        let rotated = arc(&arcs().transformed(&Matrix3x2F::rotation(30f32.to_radians(), (0.0, 0.0))));
+        assert!((rotated.rotation_angle - 50.0).abs() < 1e-3);
+        assert!((rotated.size.width - 80.0).abs() < 1e-3 && (rotated.size.height - 40.0).abs() < 1e-3);
+
+        let mirrored = arc(&arcs().transformed(&Matrix3x2F::scale((-1.0, 1.0), (0.0, 0.0))));
+        assert_eq!(mirrored.sweep_direction, SweepDirection::CounterClockwise);
+        assert_eq!(mirrored.arc_size, ArcSize::Large);
+        assert!((mirrored.rotation_angle + 20.0).abs() < 1e-3);
+    }
+}
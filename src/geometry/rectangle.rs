@@ -0,0 +1,23 @@
+use geometry::{Geometry, GeometryKind};
+use math;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rectangle {
+    rect: math::RectF,
+}
+
+impl Rectangle {
+    pub fn create(rect: &math::RectF) -> Rectangle {
+        Rectangle { rect: *rect }
+    }
+
+    pub fn get_rect(&self) -> math::RectF {
+        self.rect
+    }
+}
+
+impl Geometry for Rectangle {
+    fn kind(&self) -> GeometryKind<'_> {
+        GeometryKind::Rectangle(&self.rect)
+    }
+}
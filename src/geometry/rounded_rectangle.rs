@@ -0,0 +1,23 @@
+use geometry::{Geometry, GeometryKind};
+use math;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundedRectangle {
+    rect: math::RoundedRect,
+}
+
+impl RoundedRectangle {
+    pub fn create(rect: &math::RoundedRect) -> RoundedRectangle {
+        RoundedRectangle { rect: *rect }
+    }
+
+    pub fn get_rounded_rect(&self) -> math::RoundedRect {
+        self.rect
+    }
+}
+
+impl Geometry for RoundedRectangle {
+    fn kind(&self) -> GeometryKind<'_> {
+        GeometryKind::RoundedRectangle(&self.rect)
+    }
+}
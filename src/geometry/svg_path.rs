@@ -0,0 +1,293 @@
+//! Reading SVG path data, the `d` attribute of `<path>`, into paths.
+//!
+//! All the commands of SVG 1.1 are understood, in absolute and relative form, and
+//! map onto the segments of the builder: `H` and `V` become lines, `S` and `T` curves
+//! whose first control point is the reflection of the previous one, and `A` arcs,
+//! or lines when a radius is zero; arcs ending where they start are left out.
+//! Subpaths become filled figures, closed by `Z` and open otherwise, since SVG fills
+//! open subpaths as well; a move that no drawing command follows adds nothing.
+
+use enums::{ArcSize, FigureBegin, FigureEnd, SweepDirection};
+use geometry::path::Segment;
+use geometry::Path;
+use math::*;
+
+/// Builds a path from SVG path data, or describes the first error in it.
+pub fn parse(data: &str) -> Result<Path, String> {
+    let mut path = Path::new();
+    {
+        let mut builder = path.open();
+        for subpath in subpaths(data)? {
+            let end = if subpath.closed { FigureEnd::Closed } else { FigureEnd::Open };
+            let mut figure = builder.begin_figure(subpath.start, FigureBegin::Filled, end);
+            for segment in &subpath.segments {
+                figure = match *segment {
+                    Segment::Line(point) => figure.add_line(point),
+                    Segment::Bezier(ref bezier) => figure.add_bezier(bezier),
+                    Segment::QuadBezier(ref bezier) => figure.add_quadratic_bezier(bezier),
+                    Segment::Arc(ref arc) => figure.add_arc(arc),
+                };
+            }
+            builder = figure.end();
+        }
+    }
+    Ok(path)
+}
+
+/// A figure is only begun once the data says whether it is closed.
+struct Subpath {
+    start: Point2F,
+    segments: Vec<Segment>,
+    closed: bool,
+}
+
+fn subpaths(data: &str) -> Result<Vec<Subpath>, String> {
+    let mut parser = Parser { data: data.as_bytes(), position: 0 };
+    let mut subpaths: Vec<Subpath> = Vec::new();
+    // Whether the last subpath still takes segments, which it stops doing once closed.
+    let mut open = false;
+    let mut current = Point2F::origin();
+    let mut command = None;
+    // Control points the smooth curves reflect, left by the previous segment.
+    let (mut cubic_control, mut quad_control) = (None, None);
+    while !parser.at_end() {
+        let letter = match parser.command()? {
+            Some(letter) => letter,
+            // Parameters without a command repeat the last one, and moves turn
+            // into lines.
+            None => match command {
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(b'Z') | Some(b'z') | None => return Err(parser.error("expected a command")),
+                Some(letter) => letter,
+            },
+        };
+        if command.is_none() && letter != b'M' && letter != b'm' {
+            return Err(parser.error("path data must start with a move"));
+        }
+        command = Some(letter);
+        let origin = if letter.is_ascii_lowercase() { current - Point2F::origin() } else { Vector2F::zero() };
+        let upper = letter.to_ascii_uppercase();
+        if upper == b'M' {
+            current = parser.point()? + origin;
+            subpaths.push(Subpath { start: current, segments: Vec::new(), closed: false });
+            open = true;
+            cubic_control = None;
+            quad_control = None;
+            continue;
+        }
+        if upper == b'Z' {
+            if let Some(subpath) = subpaths.last_mut() {
+                subpath.closed = true;
+                current = subpath.start;
+            }
+            open = false;
+            cubic_control = None;
+            quad_control = None;
+            continue;
+        }
+        // Drawing after a close starts over from where the closed subpath began.
+        if !open {
+            subpaths.push(Subpath { start: current, segments: Vec::new(), closed: false });
+            open = true;
+        }
+        let (segment, cubic, quad) = match upper {
+            b'L' => (Segment::Line(parser.point()? + origin), None, None),
+            b'H' => (Segment::Line(Point2F::new(parser.number()? + origin.x, current.y)), None, None),
+            b'V' => (Segment::Line(Point2F::new(current.x, parser.number()? + origin.y)), None, None),
+            b'C' | b'S' => {
+                let point1 = match upper {
+                    b'C' => parser.point()? + origin,
+                    _ => reflect(cubic_control, current),
+                };
+                let (point2, point3) = (parser.point()? + origin, parser.point()? + origin);
+                (Segment::Bezier(BezierSegment::new(point1, point2, point3)), Some(point2), None)
+            }
+            b'Q' | b'T' => {
+                let point1 = match upper {
+                    b'Q' => parser.point()? + origin,
+                    _ => reflect(quad_control, current),
+                };
+                let point2 = parser.point()? + origin;
+                (Segment::QuadBezier(QuadBezierSegment::new(point1, point2)), None, Some(point1))
+            }
+            _ => {
+                let (radius_x, radius_y, angle) = (parser.number()?.abs(), parser.number()?.abs(), parser.number()?);
+                let arc_size = if parser.flag()? { ArcSize::Large } else { ArcSize::Small };
+                let sweep = if parser.flag()? { SweepDirection::Clockwise } else { SweepDirection::CounterClockwise };
+                let point = parser.point()? + origin;
+                // An arc back to where it starts draws nothing (SVG 1.1, F.6.2).
+                if point == current {
+                    cubic_control = None;
+                    quad_control = None;
+                    continue;
+                }
+                if radius_x == 0.0 || radius_y == 0.0 {
+                    (Segment::Line(point), None, None)
+                } else {
+                    (Segment::Arc(ArcSegment::new(point, (radius_x, radius_y), angle, sweep, arc_size)), None, None)
+                }
+            }
+        };
+        current = segment.end_point();
+        cubic_control = cubic;
+        quad_control = quad;
+        subpaths.last_mut().unwrap().segments.push(segment);
+    }
+    subpaths.retain(|subpath| !subpath.segments.is_empty());
+    Ok(subpaths)
+}
+
+/// The reflection of `control` about `point`, or `point` itself when the previous
+/// segment left no control point to reflect.
+fn reflect(control: Option<Point2F>, point: Point2F) -> Point2F {
+    match control {
+        Some(control) => point + (point - control),
+        None => point,
+    }
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Skips whitespace and commas, reporting whether anything is left.
+    fn at_end(&mut self) -> bool {
+        while self.position < self.data.len() && (self.data[self.position].is_ascii_whitespace() || self.data[self.position] == b',') {
+            self.position += 1;
+        }
+        self.position == self.data.len()
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        if self.at_end() {
+            None
+        } else {
+            Some(self.data[self.position])
+        }
+    }
+
+    /// The command letter that comes next, if parameters do not.
+    fn command(&mut self) -> Result<Option<u8>, String> {
+        match self.peek() {
+            Some(letter) if b"MmZzLlHhVvCcSsQqTtAa".contains(&letter) => {
+                self.position += 1;
+                Ok(Some(letter))
+            }
+            Some(letter) if letter.is_ascii_alphabetic() => Err(self.error(&format!("unknown command `{}`", letter as char))),
+            _ => Ok(None),
+        }
+    }
+
+    fn number(&mut self) -> Result<f32, String> {
+        if self.peek().is_none() {
+            return Err(self.error("expected a number"));
+        }
+        let start = self.position;
+        let digits = |parser: &mut Parser| {
+            let from = parser.position;
+            while parser.position < parser.data.len() && parser.data[parser.position].is_ascii_digit() {
+                parser.position += 1;
+            }
+            parser.position > from
+        };
+        self.skip_sign();
+        let mut mantissa = digits(self);
+        if self.data.get(self.position) == Some(&b'.') {
+            self.position += 1;
+            mantissa |= digits(self);
+        }
+        if !mantissa {
+            self.position = start;
+            return Err(self.error("expected a number"));
+        }
+        // An exponent only counts when digits follow it.
+        if let Some(b'e') | Some(b'E') = self.data.get(self.position) {
+            let before = self.position;
+            self.position += 1;
+            self.skip_sign();
+            if !digits(self) {
+                self.position = before;
+            }
+        }
+        let text = ::std::str::from_utf8(&self.data[start..self.position]).unwrap();
+        text.parse().map_err(|_| Parser { data: self.data, position: start }.error("expected a number"))
+    }
+
+    /// Arc flags are single digits, which may run into the next parameter.
+    fn flag(&mut self) -> Result<bool, String> {
+        match self.peek() {
+            Some(b'0') => {
+                self.position += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.position += 1;
+                Ok(true)
+            }
+            _ => Err(self.error("expected a flag, 0 or 1")),
+        }
+    }
+
+    fn point(&mut self) -> Result<Point2F, String> {
+        Ok(Point2F::new(self.number()?, self.number()?))
+    }
+
+    fn skip_sign(&mut self) {
+        if let Some(b'+') | Some(b'-') = self.data.get(self.position) {
+            self.position += 1;
+        }
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("invalid path data at offset {}: {}", self.position, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(path: &Path) -> Vec<Segment> {
+        path.figures().iter().flat_map(|figure| figure.segments.clone()).collect()
+    }
+
+    #[test]
+    fn lines_and_subpaths() {
+        let path = parse("M10,10 l20-10 3e1 0 H100 v50 z m5 5 L0 0 M.5.5").unwrap();
+        let figures = path.figures();
+        // The relative move after `z` starts from the closed subpath's start, and
+        // the trailing move is dropped.
+        assert_eq!(figures.len(), 2);
+        assert_eq!((figures[0].start, figures[0].end), (Point2F::new(10.0, 10.0), FigureEnd::Closed));
+        assert_eq!((figures[1].start, figures[1].end), (Point2F::new(15.0, 15.0), FigureEnd::Open));
+        let ends: Vec<Point2F> = segments(&path).iter().map(Segment::end_point).collect();
+        let expected = [(30.0, 0.0), (60.0, 0.0), (100.0, 0.0), (100.0, 50.0), (0.0, 0.0)];
+        assert_eq!(ends, expected.iter().map(|&point| point.into()).collect::<Vec<Point2F>>());
+    }
+
+    #[test]
+    fn smooth_curves_and_arcs() {
+        let path = parse("M100 600 C50 50 600 50 900 600 s100 100 200 0 Q400 0 400 300 T900 600 a50 25 30 1 0 0 0 a50 25 30 1 0 100 0 A0 5 0 00 10 10").unwrap();
+        let segments = segments(&path);
+        assert_eq!(segments[1], Segment::Bezier(BezierSegment::new((1200.0, 1150.0), (1000.0, 700.0), (1100.0, 600.0))));
+        assert_eq!(segments[3], Segment::QuadBezier(QuadBezierSegment::new((400.0, 600.0), (900.0, 600.0))));
+        // The arc back to its own start is left out.
+        assert_eq!(segments.len(), 6);
+        let arc = ArcSegment::new((1000.0, 600.0), (50.0, 25.0), 30.0, SweepDirection::CounterClockwise, ArcSize::Large);
+        assert_eq!(segments[4], Segment::Arc(arc));
+        // A zero radius draws a line, and packed flags still read as two.
+        assert_eq!(segments[5], Segment::Line((10.0, 10.0).into()));
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(parse("L10 10").unwrap_err(), "invalid path data at offset 1: path data must start with a move");
+        assert_eq!(parse("M10 10 L20").unwrap_err(), "invalid path data at offset 10: expected a number");
+        assert_eq!(parse("M10 10 X").unwrap_err(), "invalid path data at offset 7: unknown command `X`");
+        assert_eq!(parse("M0 0 A1 1 0 2 0 5 5").unwrap_err(), "invalid path data at offset 12: expected a flag, 0 or 1");
+        assert_eq!(parse("").unwrap().get_figure_count(), 0);
+    }
+}
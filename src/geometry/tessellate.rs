@@ -0,0 +1,226 @@
+//! Breaking fills into triangles, like `ID2D1Geometry::Tessellate`.
+//!
+//! The filled figures are flattened within the tolerance and cut into horizontal
+//! slabs at every vertex and every crossing of two edges, so no edge starts, ends or
+//! crosses another inside a slab. Crossings are found sweeping down the edges in order
+//! of their tops, which only pairs edges that overlap vertically. The edges running
+//! through a slab are then ordered left to right and the fill rule picks the
+//! trapezoids between them, which are split in two triangles each.
+//!
+//! Figures with a point that is not finite are left out whole: dropping only the edges
+//! at that point would leave the rest of the figure open and unbalance the winding.
+
+use enums::FillMode;
+use geometry::flatten;
+use geometry::Geometry;
+use math::*;
+
+/// Points are cut in double precision, like they are combined.
+type Point = (f64, f64);
+
+/// Edge of a filled figure, stored top to bottom.
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    top: Point,
+    bottom: Point,
+    /// Winding change when crossing the edge from left to right: figures wound
+    /// clockwise on screen go up their left side.
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: f64) -> f64 {
+        let (top, bottom) = (self.top, self.bottom);
+        top.0 + (bottom.0 - top.0) * (y - top.1) / (bottom.1 - top.1)
+    }
+}
+
+/// Triangles covering the fill of the geometry drawn with `transform`, following its
+/// fill mode. Triangles do not overlap and all wind clockwise on screen.
+pub fn tessellate(geometry: &dyn Geometry, transform: Option<&Matrix3x2F>, tolerance: f32) -> Vec<Triangle> {
+    let edges = edges(geometry, transform, tolerance);
+    let mut ys: Vec<f64> = edges.iter().flat_map(|edge| vec![edge.top.1, edge.bottom.1]).collect();
+    ys.extend(crossings(&edges));
+    ys.sort_by(f64::total_cmp);
+    ys.dedup();
+
+    let mode = geometry.get_fill_mode();
+    let mut triangles = Vec::new();
+    for pair in ys.windows(2) {
+        let (y0, y1) = (pair[0], pair[1]);
+        let middle = (y0 + y1) / 2.0;
+        let mut active: Vec<&Edge> = edges.iter().filter(|edge| edge.top.1 <= y0 && edge.bottom.1 >= y1).collect();
+        active.sort_by(|a, b| a.x_at(middle).total_cmp(&b.x_at(middle)));
+        let mut winding = 0;
+        for pair in active.windows(2) {
+            winding += pair[0].winding;
+            let inside = match mode {
+                FillMode::Alternate => winding % 2 != 0,
+                FillMode::Winding => winding != 0,
+            };
+            if inside {
+                let (left, right) = (pair[0], pair[1]);
+                trapezoid(
+                    [(left.x_at(y0), y0), (right.x_at(y0), y0), (right.x_at(y1), y1), (left.x_at(y1), y1)],
+                    &mut triangles,
+                );
+            }
+        }
+    }
+    triangles
+}
+
+/// The non-horizontal edges of the filled figures, closed implicitly, leaving out the
+/// figures with a point that is not finite.
+fn edges(geometry: &dyn Geometry, transform: Option<&Matrix3x2F>, tolerance: f32) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for polyline in flatten::flatten_transformed(geometry, transform, tolerance) {
+        if !polyline.filled || !polyline.points.iter().all(|point| point.x.is_finite() && point.y.is_finite()) {
+            continue;
+        }
+        let points: Vec<Point> = polyline.points.iter().map(|point| (f64::from(point.x), f64::from(point.y))).collect();
+        for (i, &from) in points.iter().enumerate() {
+            let to = points[(i + 1) % points.len()];
+            if from.1 < to.1 {
+                edges.push(Edge { top: from, bottom: to, winding: -1 });
+            } else if from.1 > to.1 {
+                edges.push(Edge { top: to, bottom: from, winding: 1 });
+            }
+        }
+    }
+    edges
+}
+
+/// Heights at which edges cross. Sweeping down the edges in order of their tops, each
+/// is only tried against those still running at its top.
+fn crossings(edges: &[Edge]) -> Vec<f64> {
+    let mut order: Vec<&Edge> = edges.iter().collect();
+    order.sort_by(|a, b| a.top.1.total_cmp(&b.top.1));
+    let mut active: Vec<&Edge> = Vec::new();
+    let mut ys = Vec::new();
+    for edge in order {
+        active.retain(|other| other.bottom.1 > edge.top.1);
+        ys.extend(active.iter().filter_map(|other| crossing(edge, other)));
+        active.push(edge);
+    }
+    ys
+}
+
+/// Height at which two edges cross strictly between their ends, if they do.
+fn crossing(a: &Edge, b: &Edge) -> Option<f64> {
+    let (top, bottom) = (a.top.1.max(b.top.1), a.bottom.1.min(b.bottom.1));
+    if top >= bottom {
+        return None;
+    }
+    // Edges whose horizontal extents are apart cannot cross.
+    let extent = |edge: &Edge| (edge.top.0.min(edge.bottom.0), edge.top.0.max(edge.bottom.0));
+    let ((a_left, a_right), (b_left, b_right)) = (extent(a), extent(b));
+    if a_right < b_left || b_right < a_left {
+        return None;
+    }
+    let (d_top, d_bottom) = (a.x_at(top) - b.x_at(top), a.x_at(bottom) - b.x_at(bottom));
+    if d_top * d_bottom >= 0.0 {
+        return None;
+    }
+    let y = top + (bottom - top) * d_top / (d_top - d_bottom);
+    if y > top && y < bottom {
+        Some(y)
+    } else {
+        None
+    }
+}
+
+/// Adds the triangles of a trapezoid given clockwise from its top left corner,
+/// leaving out those that are empty once in single precision: a pointed end makes one
+/// empty, and crossings just off a vertex leave slabs too thin to keep.
+fn trapezoid(corners: [Point; 4], triangles: &mut Vec<Triangle>) {
+    let [top_left, top_right, bottom_right, bottom_left] = corners.map(|(x, y)| Point2F::new(x as f32, y as f32));
+    let mut add = |a: Point2F, b: Point2F, c: Point2F| {
+        let (ab, ac) = (b - a, c - a);
+        if ab.x * ac.y - ab.y * ac.x > 0.0 {
+            triangles.push(Triangle::new(a, b, c));
+        }
+    };
+    add(top_left, top_right, bottom_right);
+    add(top_left, bottom_right, bottom_left);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enums::{FigureBegin, FigureEnd};
+    use geometry::fixtures::{nested_squares, triangle_area as area};
+    use geometry::flatten::DEFAULT_FLATTENING_TOLERANCE as TOLERANCE;
+    use geometry::{Ellipse, Path};
+
+    #[test]
+    fn areas_follow_fill_mode() {
+        let nested = |fill_mode| {
+            let mut path = nested_squares(fill_mode, 25.0);
+            // Hollow figures are not filled.
+            path.open()
+                .begin_figure((200.0, 0.0), FigureBegin::Hollow, FigureEnd::Closed)
+                .add_lines(&[(300.0, 0.0).into(), (300.0, 100.0).into()])
+                .end();
+            path
+        };
+        assert_eq!(area(&nested(FillMode::Alternate).tessellate(None, TOLERANCE)), 7500.0);
+        assert_eq!(area(&nested(FillMode::Winding).tessellate(None, TOLERANCE)), 10000.0);
+    }
+
+    #[test]
+    fn cuts_crossing_edges() {
+        // A bow tie, whose loops wind opposite ways, and the same with a transform.
+        let mut bow_tie = Path::new();
+        bow_tie
+            .open()
+            .begin_figure((0.0, 0.0), FigureBegin::Filled, FigureEnd::Closed)
+            .add_lines(&[(100.0, 100.0).into(), (100.0, 0.0).into(), (0.0, 100.0).into()])
+            .end();
+        assert_eq!(area(&bow_tie.tessellate(None, TOLERANCE)), 5000.0);
+        let double = Matrix3x2F::scale((2.0, 2.0), (0.0, 0.0));
+        assert_eq!(area(&bow_tie.tessellate(Some(&double), TOLERANCE)), 20000.0);
+
+        // Curves cover the area of their flattened figure.
+        let ellipse = Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 100.0, 50.0));
+        let flattened = ellipse.simplify(TOLERANCE).figures()[0].signed_area(TOLERANCE);
+        assert!((area(&ellipse.tessellate(None, TOLERANCE)) - flattened).abs() < flattened * 1e-4);
+    }
+
+    #[test]
+    fn finds_every_crossing() {
+        // A star drawn in one stroke crosses itself ten times around a pentagon, which
+        // only the winding rule fills.
+        let corner = |i: usize| {
+            let angle = (-90.0 + 144.0 * i as f32).to_radians();
+            Point2F::new(100.0 * angle.cos(), 100.0 * angle.sin())
+        };
+        let star = |fill_mode| {
+            let mut path = Path::new();
+            path.open()
+                .fill_mode(fill_mode)
+                .begin_figure(corner(0), FigureBegin::Filled, FigureEnd::Closed)
+                .add_lines(&(1..5).map(corner).collect::<Vec<_>>())
+                .end();
+            path.tessellate(None, TOLERANCE)
+        };
+        let inner = 100.0 * 72f32.to_radians().cos() / 36f32.to_radians().cos();
+        let pentagon = 2.5 * inner * inner * 72f32.to_radians().sin();
+        let difference = area(&star(FillMode::Winding)) - area(&star(FillMode::Alternate));
+        assert!((difference - pentagon).abs() < 1e-2, "{} != {}", difference, pentagon);
+    }
+
+    #[test]
+    fn skips_figures_with_points_that_are_not_finite() {
+        // Without their bad points, the square's sides would still fill between them.
+        let mut path = nested_squares(FillMode::Winding, 25.0);
+        path.open()
+            .begin_figure((200.0, 0.0), FigureBegin::Filled, FigureEnd::Closed)
+            .add_lines(&[(300.0, 0.0).into(), (300.0, 100.0).into(), (f32::NAN, 50.0).into(), (200.0, 100.0).into()])
+            .end()
+            .begin_figure((400.0, 0.0), FigureBegin::Filled, FigureEnd::Closed)
+            .add_lines(&[(500.0, 0.0).into(), (500.0, 100.0).into(), (450.0, f32::INFINITY).into(), (400.0, 100.0).into()])
+            .end();
+        assert_eq!(area(&path.tessellate(None, TOLERANCE)), 10000.0);
+    }
+}
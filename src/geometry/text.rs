@@ -0,0 +1,240 @@
+//! A compact text form of paths that reads back exactly, for fixtures and logs.
+//!
+//! It is absolute SVG path data with two additions: hollow figures begin with `O`
+//! instead of `M`, and paths filled with the winding rule start with `F1`, as in XAML
+//! path markup (`F0`, the alternate rule, is the default and left out). Coordinates are
+//! written as the shortest decimals that parse back to the same `f32`. For instance:
+//!
+//! ```text
+//! F1 M0,0 L100,0 Q100,50 50,50 Z O0,80 A40,20 0 0 1 80,80
+//! ```
+//!
+//! Reading accepts any mix of whitespace and commas between parameters, and keeps
+//! figures without segments, which SVG drops.
+
+use enums::{ArcSize, FigureBegin, FigureEnd, FillMode, SweepDirection};
+use geometry::path::Segment;
+use geometry::{Geometry, Path};
+use math::*;
+
+use std::fmt::{self, Write};
+
+/// Writes the text form of `path`.
+pub fn write(path: &Path, f: &mut dyn Write) -> fmt::Result {
+    let mut separator = "";
+    if path.get_fill_mode() == FillMode::Winding {
+        f.write_str("F1")?;
+        separator = " ";
+    }
+    for figure in path.figures() {
+        let begin = if figure.is_filled() { 'M' } else { 'O' };
+        write!(f, "{}{}{}", separator, begin, point(figure.start))?;
+        separator = " ";
+        for segment in &figure.segments {
+            match *segment {
+                Segment::Line(p) => write!(f, " L{}", point(p))?,
+                Segment::Bezier(ref b) => write!(f, " C{} {} {}", point(b.point1), point(b.point2), point(b.point3))?,
+                Segment::QuadBezier(ref b) => write!(f, " Q{} {}", point(b.point1), point(b.point2))?,
+                Segment::Arc(ref arc) => write!(
+                    f,
+                    " A{},{} {} {} {} {}",
+                    arc.size.width,
+                    arc.size.height,
+                    arc.rotation_angle,
+                    if arc.arc_size == ArcSize::Large { 1 } else { 0 },
+                    if arc.sweep_direction == SweepDirection::Clockwise { 1 } else { 0 },
+                    point(arc.point)
+                )?,
+            }
+        }
+        if figure.is_closed() {
+            f.write_str(" Z")?;
+        }
+    }
+    Ok(())
+}
+
+fn point(point: Point2F) -> String {
+    format!("{},{}", point.x, point.y)
+}
+
+/// Reads a path back from its text form.
+pub fn parse(text: &str) -> Result<Path, String> {
+    let mut tokens = Tokens::new(text);
+    let mut path = Path::new();
+    {
+        let mut builder = path.open();
+        if tokens.peek_command() == Some('F') {
+            tokens.command()?;
+            let fill_mode = match tokens.next()? {
+                "0" => FillMode::Alternate,
+                "1" => FillMode::Winding,
+                token => return Err(format!("invalid path text: expected a fill mode, 0 or 1, found `{}`", token)),
+            };
+            builder = builder.fill_mode(fill_mode);
+        }
+        while tokens.peek_command().is_some() {
+            let begin = match tokens.command()? {
+                'M' => FigureBegin::Filled,
+                'O' => FigureBegin::Hollow,
+                command => return Err(format!("invalid path text: expected a figure, found `{}`", command)),
+            };
+            let start = tokens.point()?;
+            let mut segments = Vec::new();
+            let mut end = FigureEnd::Open;
+            loop {
+                let segment = match tokens.peek_command() {
+                    Some('L') => Segment::Line(tokens.skip().point()?),
+                    Some('C') => {
+                        let tokens = tokens.skip();
+                        Segment::Bezier(BezierSegment::new(tokens.point()?, tokens.point()?, tokens.point()?))
+                    }
+                    Some('Q') => {
+                        let tokens = tokens.skip();
+                        Segment::QuadBezier(QuadBezierSegment::new(tokens.point()?, tokens.point()?))
+                    }
+                    Some('A') => {
+                        let tokens = tokens.skip();
+                        let size = SizeF::new(tokens.number()?, tokens.number()?);
+                        let angle = tokens.number()?;
+                        let arc_size = if tokens.flag()? { ArcSize::Large } else { ArcSize::Small };
+                        let sweep = if tokens.flag()? { SweepDirection::Clockwise } else { SweepDirection::CounterClockwise };
+                        Segment::Arc(ArcSegment::new(tokens.point()?, size, angle, sweep, arc_size))
+                    }
+                    Some('Z') => {
+                        tokens.skip();
+                        end = FigureEnd::Closed;
+                        break;
+                    }
+                    _ => break,
+                };
+                segments.push(segment);
+            }
+            let mut figure = builder.begin_figure(start, begin, end);
+            for segment in &segments {
+                figure = match *segment {
+                    Segment::Line(point) => figure.add_line(point),
+                    Segment::Bezier(ref bezier) => figure.add_bezier(bezier),
+                    Segment::QuadBezier(ref bezier) => figure.add_quadratic_bezier(bezier),
+                    Segment::Arc(ref arc) => figure.add_arc(arc),
+                };
+            }
+            builder = figure.end();
+        }
+    }
+    match tokens.peek() {
+        Some(token) => Err(format!("invalid path text: unexpected `{}`", token)),
+        None => Ok(path),
+    }
+}
+
+/// Parameters split at whitespace and commas, with command letters split from the
+/// parameter they are written against.
+struct Tokens<'a> {
+    tokens: Vec<&'a str>,
+    index: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(text: &'a str) -> Tokens<'a> {
+        let mut tokens = Vec::new();
+        for mut token in text.split(|c: char| c.is_whitespace() || c == ',').filter(|token| !token.is_empty()) {
+            while token.len() > 1 && token.starts_with(is_command) {
+                tokens.push(&token[..1]);
+                token = &token[1..];
+            }
+            tokens.push(token);
+        }
+        Tokens { tokens, index: 0 }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.index).cloned()
+    }
+
+    fn next(&mut self) -> Result<&'a str, String> {
+        let token = self.peek().ok_or("invalid path text: unexpected end")?;
+        self.index += 1;
+        Ok(token)
+    }
+
+    fn skip(&mut self) -> &mut Self {
+        self.index += 1;
+        self
+    }
+
+    fn peek_command(&self) -> Option<char> {
+        self.peek().and_then(|token| token.chars().next()).filter(|&c| is_command(c))
+    }
+
+    fn command(&mut self) -> Result<char, String> {
+        let token = self.next()?;
+        Ok(token.chars().next().unwrap())
+    }
+
+    fn number(&mut self) -> Result<f32, String> {
+        let token = self.next()?;
+        token.parse().map_err(|_| format!("invalid path text: expected a number, found `{}`", token))
+    }
+
+    fn flag(&mut self) -> Result<bool, String> {
+        match self.next()? {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            token => Err(format!("invalid path text: expected a flag, 0 or 1, found `{}`", token)),
+        }
+    }
+
+    fn point(&mut self) -> Result<Point2F, String> {
+        Ok(Point2F::new(self.number()?, self.number()?))
+    }
+}
+
+/// Letters that start commands. None of them starts the `inf` and `NaN` that
+/// non-finite coordinates are written as.
+fn is_command(c: char) -> bool {
+    "FMOLCQAZ".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_exactly() {
+        let mut path = Path::new();
+        path.open()
+            .fill_mode(FillMode::Winding)
+            .begin_figure((0.1, -0.0), FigureBegin::Filled, FigureEnd::Closed)
+            .add_line((1.0 / 3.0, 1e-7))
+            .add_bezier(&BezierSegment::new((f32::MAX, 2.5), (-1e30, 0.3), (7.0, 8.0)))
+            .add_quadratic_bezier(&QuadBezierSegment::new((1.0, 2.0), (3.0, 4.0)))
+            .end()
+            .begin_figure((5.0, 5.0), FigureBegin::Hollow, FigureEnd::Open)
+            .add_arc(&ArcSegment::new((9.0, 9.0), (4.0, 2.0), 33.3, SweepDirection::CounterClockwise, ArcSize::Large))
+            .end()
+            .begin_figure((0.0, 0.0), FigureBegin::Filled, FigureEnd::Open)
+            .end();
+        let text = path.to_string();
+        let parsed: Path = text.parse().unwrap();
+        assert_eq!(parsed, path);
+        assert_eq!(parsed.to_string(), text);
+        // The sign of zero survives, which equality alone does not show.
+        assert!(parsed.figures()[0].start.y.is_sign_negative());
+    }
+
+    #[test]
+    fn reads_and_writes_text() {
+        let text = "F1 M0,0 L100,0 Q100,50 50,50 Z O0,80 A40,20 0 0 1 80,80";
+        let path: Path = text.parse().unwrap();
+        assert_eq!(path.get_fill_mode(), FillMode::Winding);
+        assert_eq!(path.figures()[1].begin, FigureBegin::Hollow);
+        assert_eq!(path.to_string(), text);
+        // Spacing is free, and alternate paths are plain SVG path data.
+        let spaced: Path = "M 0 0 , L 100 0 ZM1,1".parse().unwrap();
+        assert_eq!(spaced.to_string(), "M0,0 L100,0 Z M1,1");
+        assert_eq!("M0,0 L1".parse::<Path>().unwrap_err(), "invalid path text: unexpected end");
+        assert_eq!("M0,0 X1,1".parse::<Path>().unwrap_err(), "invalid path text: unexpected `X1`");
+        assert_eq!("M0,0 Lx,1".parse::<Path>().unwrap_err(), "invalid path text: expected a number, found `x`");
+    }
+}
@@ -0,0 +1,40 @@
+use enums::FillMode;
+use geometry::{Geometry, GeometryKind, Path};
+use math;
+
+/// A geometry drawn with a transform of its own, like `ID2D1TransformedGeometry`.
+///
+/// Only the outline is transformed: strokes keep the width they are drawn with, as in
+/// Direct2D.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransformedGeometry {
+    source: Path,
+    transform: math::Matrix3x2F,
+}
+
+impl TransformedGeometry {
+    pub fn create(source: &dyn Geometry, transform: &math::Matrix3x2F) -> TransformedGeometry {
+        TransformedGeometry {
+            source: Path::from_geometry(source),
+            transform: *transform,
+        }
+    }
+
+    pub fn get_source_geometry(&self) -> &Path {
+        &self.source
+    }
+
+    pub fn get_transform(&self) -> math::Matrix3x2F {
+        self.transform
+    }
+}
+
+impl Geometry for TransformedGeometry {
+    fn kind(&self) -> GeometryKind<'_> {
+        GeometryKind::Transformed(self)
+    }
+
+    fn get_fill_mode(&self) -> FillMode {
+        self.source.get_fill_mode()
+    }
+}
@@ -0,0 +1,268 @@
+//! Turning strokes into fills, like `ID2D1Geometry::Widen`.
+//!
+//! The stroke of a flattened figure, or of each of its dashes, is covered by simple
+//! polygons: a quad along every segment, a wedge at every join and a cap at both ends
+//! of open figures. They all
+//! wind clockwise, so filling them with the non-zero rule paints the stroke; `widen`
+//! merges them into a single outline for callers that fill with other rules.
+
+use enums::{CapStyle, LineJoin};
+use geometry::combine;
+use geometry::dash;
+use geometry::flatten::{self, Polyline};
+use geometry::{Geometry, Path};
+use math::*;
+use stroke_style::StrokeStyle;
+
+use std::f32::consts::PI;
+
+/// The stroke of the geometry as a path to fill. The pen is transformed along with the
+/// geometry, as when drawing with a transform, so the stroke is widened first and
+/// flattened finely enough for `tolerance` to hold once transformed.
+pub fn widen(
+    geometry: &dyn Geometry,
+    stroke_width: f32,
+    stroke_style: Option<&StrokeStyle>,
+    transform: Option<&Matrix3x2F>,
+    tolerance: f32,
+) -> Path {
+    let tolerance = flatten::untransformed_tolerance(tolerance, transform);
+    let default_style = StrokeStyle::default();
+    let style = stroke_style.unwrap_or(&default_style);
+    let polygons = outline(&flatten::flatten(geometry, tolerance), stroke_width, style, tolerance);
+    let path = combine::union(&polygons);
+    match transform {
+        Some(matrix) => path.transformed(matrix),
+        None => path,
+    }
+}
+
+/// Polygons covering the stroke of the given figures, all wound clockwise.
+/// Round joins and caps stay within `tolerance` of true circles.
+pub fn outline(polylines: &[Polyline], stroke_width: f32, style: &StrokeStyle, tolerance: f32) -> Vec<Vec<Point2F>> {
+    let pen = Pen {
+        half: stroke_width.abs() / 2.0,
+        join: style.get_line_join(),
+        miter_limit: style.get_miter_limit().max(1.0),
+        tolerance,
+    };
+    let mut polygons = Vec::new();
+    if pen.half == 0.0 {
+        return polygons;
+    }
+    match dash::dashes(polylines, stroke_width, style) {
+        Some(dashes) => {
+            for dash in dashes {
+                match dash.points.len() {
+                    1 => pen.dot(dash.points[0], dash.direction, dash.start_cap, dash.end_cap, &mut polygons),
+                    _ => pen.stroke(&dash.points, false, dash.start_cap, dash.end_cap, &mut polygons),
+                }
+            }
+        }
+        None => {
+            for polyline in polylines {
+                let (start_cap, end_cap) = (style.get_start_cap(), style.get_end_cap());
+                pen.stroke(&polyline.points, polyline.closed, start_cap, end_cap, &mut polygons);
+            }
+        }
+    }
+    for polygon in &mut polygons {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+    polygons
+}
+
+struct Pen {
+    half: f32,
+    join: LineJoin,
+    miter_limit: f32,
+    tolerance: f32,
+}
+
+impl Pen {
+    fn stroke(&self, points: &[Point2F], closed: bool, start_cap: CapStyle, end_cap: CapStyle, polygons: &mut Vec<Vec<Point2F>>) {
+        let mut count = points.len();
+        if closed && count > 2 && points[0] == points[count - 1] {
+            count -= 1;
+        }
+        if count == 1 && !closed {
+            // A lone point only shows its caps, which face along the x axis.
+            self.dot(points[0], Vector2F::new(1.0, 0.0), start_cap, end_cap, polygons);
+            return;
+        }
+        if count < 2 {
+            return;
+        }
+        let segments = if closed { count } else { count - 1 };
+        for i in 0..segments {
+            let (p0, p1) = (points[i], points[(i + 1) % count]);
+            let normal = perpendicular(direction(p0, p1)) * self.half;
+            polygons.push(vec![p0 + normal, p1 + normal, p1 - normal, p0 - normal]);
+        }
+        let joins = if closed { 0..count } else { 1..count - 1 };
+        for i in joins {
+            let prev = points[(i + count - 1) % count];
+            let (vertex, next) = (points[i], points[(i + 1) % count]);
+            self.join(prev, vertex, next, polygons);
+        }
+        if !closed {
+            self.cap(points[0], direction(points[1], points[0]), start_cap, polygons);
+            self.cap(points[count - 1], direction(points[count - 2], points[count - 1]), end_cap, polygons);
+        }
+    }
+
+    /// The caps of a figure of zero length heading in `direction`.
+    fn dot(&self, point: Point2F, direction: Vector2F, start_cap: CapStyle, end_cap: CapStyle, polygons: &mut Vec<Vec<Point2F>>) {
+        self.cap(point, -direction, start_cap, polygons);
+        self.cap(point, direction, end_cap, polygons);
+    }
+
+    /// The wedge filling the outer side of the corner at `vertex`.
+    fn join(&self, prev: Point2F, vertex: Point2F, next: Point2F, polygons: &mut Vec<Vec<Point2F>>) {
+        let half = self.half;
+        let (d0, d1) = (direction(prev, vertex), direction(vertex, next));
+        if self.join == LineJoin::Round {
+            polygons.push(self.circle(vertex));
+            return;
+        }
+        let cross = d0.x * d1.y - d0.y * d1.x;
+        let cos = d0.x * d1.x + d0.y * d1.y;
+        if cross == 0.0 {
+            // Going straight on needs no join, and turning back only has a miter,
+            // which is always past the limit.
+            if cos < 0.0 && self.join == LineJoin::Miter {
+                let (normal, clip) = (perpendicular(d0) * half, d0 * (half * self.miter_limit));
+                polygons.push(vec![vertex + normal, vertex + normal + clip, vertex - normal + clip, vertex - normal]);
+            }
+            return;
+        }
+        // Offsets of the two segments on the outer side of the corner.
+        let side = if cross > 0.0 { -half } else { half };
+        let (n0, n1) = (perpendicular(d0) * side, perpendicular(d1) * side);
+        // Length of the miter in multiples of half the stroke width.
+        let ratio = (2.0 / (1.0 + cos)).sqrt();
+        let polygon = match self.join {
+            LineJoin::Miter | LineJoin::MiterOrBevel if ratio.is_finite() && ratio <= self.miter_limit => {
+                let miter = (n0 + n1) * (1.0 / (1.0 + cos));
+                vec![vertex, vertex + n0, vertex + miter, vertex + n1]
+            }
+            LineJoin::Miter => {
+                // Clipped where the miter reaches the limit.
+                let bisector = (n0 + n1).unit();
+                let clip = half * self.miter_limit;
+                let along = |n: Vector2F, d: Vector2F| {
+                    // Where the offset edge `vertex + n + s d` crosses the clipping line.
+                    let s = (clip - dot(n, bisector)) / dot(d, bisector);
+                    vertex + n + d * s
+                };
+                vec![vertex, vertex + n0, along(n0, d0), along(n1, -d1), vertex + n1]
+            }
+            _ => vec![vertex, vertex + n0, vertex + n1],
+        };
+        polygons.push(polygon);
+    }
+
+    /// The cap at `end` of a figure leaving in direction `outward`.
+    fn cap(&self, end: Point2F, outward: Vector2F, cap: CapStyle, polygons: &mut Vec<Vec<Point2F>>) {
+        let (normal, reach) = (perpendicular(outward) * self.half, outward * self.half);
+        match cap {
+            CapStyle::Flat => {}
+            CapStyle::Round => polygons.push(self.circle(end)),
+            CapStyle::Square => polygons.push(vec![end + normal, end + normal + reach, end - normal + reach, end - normal]),
+            CapStyle::Triangle => polygons.push(vec![end + normal, end + reach, end - normal]),
+        }
+    }
+
+    /// A polygon inscribed in the pen's circle around `center`.
+    fn circle(&self, center: Point2F) -> Vec<Point2F> {
+        // Each side strays at most `tolerance` from the arc it replaces.
+        let step = 2.0 * (1.0 - (self.tolerance / self.half).min(1.0)).acos();
+        let sides = ((2.0 * PI / step).ceil() as usize).clamp(8, 1024);
+        (0..sides)
+            .map(|i| {
+                let (sin, cos) = (2.0 * PI * i as f32 / sides as f32).sin_cos();
+                center + Vector2F::new(cos, sin) * self.half
+            })
+            .collect()
+    }
+}
+
+fn direction(from: Point2F, to: Point2F) -> Vector2F {
+    (to - from).unit()
+}
+
+fn perpendicular(v: Vector2F) -> Vector2F {
+    Vector2F::new(-v.y, v.x)
+}
+
+fn dot(a: Vector2F, b: Vector2F) -> f32 {
+    a.x * b.x + a.y * b.y
+}
+
+fn signed_area(polygon: &[Point2F]) -> f32 {
+    let mut area = 0.0;
+    for (i, p0) in polygon.iter().enumerate() {
+        let p1 = polygon[(i + 1) % polygon.len()];
+        area += p0.x * p1.y - p1.x * p0.y;
+    }
+    area / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enums::{FigureBegin, FigureEnd};
+    use geometry::fixtures::area;
+    use geometry::flatten::DEFAULT_FLATTENING_TOLERANCE as TOLERANCE;
+    use geometry::Rectangle;
+
+    #[test]
+    fn widens_rectangle_into_frame() {
+        let rect = Rectangle::create(&RectF::new(0.0, 0.0, 100.0, 100.0));
+        let frame = rect.widen(10.0, None, None, TOLERANCE);
+        // An outer square wound one way and the inner one the other way.
+        assert_eq!(frame.get_figure_count(), 2);
+        assert_eq!(area(&frame), 110.0 * 110.0 - 90.0 * 90.0);
+        let bevel = StrokeStyle::create().with_line_join(LineJoin::Bevel).build();
+        let beveled = rect.widen(10.0, Some(&bevel), None, TOLERANCE);
+        assert_eq!(area(&beveled), area(&frame) - 4.0 * 12.5);
+    }
+
+    #[test]
+    fn widens_line_with_caps() {
+        let mut line = Path::new();
+        line.open().begin_figure((0.0, 0.0), FigureBegin::Hollow, FigureEnd::Open).add_line((100.0, 0.0)).end();
+        let caps = |start, end| {
+            let style = StrokeStyle::create().with_start_cap(start).with_end_cap(end).build();
+            area(&line.widen(10.0, Some(&style), None, TOLERANCE))
+        };
+        assert_eq!(caps(CapStyle::Flat, CapStyle::Flat), 1000.0);
+        assert_eq!(caps(CapStyle::Square, CapStyle::Triangle), 1000.0 + 50.0 + 25.0);
+        // Inscribed polygons lose at most a tolerance wide strip of the circle.
+        let (round, circle) = (caps(CapStyle::Round, CapStyle::Round), PI * 25.0);
+        assert!(round < 1000.0 + circle && round > 1000.0 + circle - 2.0 * PI * 5.0 * TOLERANCE);
+    }
+
+    #[test]
+    fn tolerance_holds_after_transform() {
+        // A unit circle drawn a hundred times larger, with a stroke 2 wide: every side
+        // of the outline stays within the tolerance of the circle it follows.
+        let circle = ::geometry::Ellipse::create(&::math::Ellipse::new((0.0, 0.0), 1.0, 1.0));
+        let zoom = Matrix3x2F::scale((100.0, 100.0), (0.0, 0.0));
+        let ring = circle.widen(0.02, None, Some(&zoom), TOLERANCE);
+        assert_eq!(ring.get_figure_count(), 2);
+        for polyline in flatten::flatten(&ring, TOLERANCE) {
+            let points = &polyline.points;
+            for (i, &p0) in points.iter().enumerate() {
+                let p1 = points[(i + 1) % points.len()];
+                for &point in &[p0, Point2F::new((p0.x + p1.x) / 2.0, (p0.y + p1.y) / 2.0)] {
+                    let radius = (point - Point2F::origin()).length();
+                    let error = (radius - 101.0).abs().min((radius - 99.0).abs());
+                    assert!(error <= TOLERANCE, "{:?} strays {}", point, error);
+                }
+            }
+        }
+    }
+}
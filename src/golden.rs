@@ -0,0 +1,155 @@
+//! Golden-image tests: every scene is rendered with the software canvas and compared
+//! with the reference PNG in `tests/golden`.
+//!
+//! Run with `UPDATE_GOLDEN=1` to (re)write the references after an intended change.
+//! On a mismatch the rendered image and a diff are written to `target/golden`.
+
+use canvas::software::{Framebuffer, SoftwareCanvas};
+use examples::{Example, Selection};
+use headless;
+use state::State;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const WIDTH: u32 = 1024;
+const HEIGHT: u32 = 768;
+
+/// Largest difference allowed on any channel of any pixel.
+const TOLERANCE: u8 = 2;
+
+fn render(example: &mut dyn Example, state: &State) -> Framebuffer {
+    let mut canvas = SoftwareCanvas::new(WIDTH, HEIGHT);
+    headless::draw_frame(example, state, &mut canvas);
+    canvas.into_framebuffer()
+}
+
+fn check(name: &str, actual: &Framebuffer) {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let reference = root.join("tests").join("golden").join(format!("{}.png", name));
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(reference.parent().unwrap()).unwrap();
+        actual.save_png(&reference).unwrap();
+        return;
+    }
+    let expected = Framebuffer::load_png(&reference).unwrap_or_else(|e| {
+        panic!("cannot read {}: {} (run with UPDATE_GOLDEN=1 to create it)", reference.display(), e)
+    });
+    assert_eq!((expected.width(), expected.height()), (actual.width(), actual.height()), "{}: size differs", name);
+
+    let (diff, mismatches) = compare(&expected, actual);
+    if mismatches > 0 {
+        let out = root.join("target").join("golden");
+        fs::create_dir_all(&out).unwrap();
+        let actual_path = out.join(format!("{}.actual.png", name));
+        let diff_path = out.join(format!("{}.diff.png", name));
+        actual.save_png(&actual_path).unwrap();
+        diff.save_png(&diff_path).unwrap();
+        panic!(
+            "{}: {} pixels differ by more than {}; see {} and {}",
+            name,
+            mismatches,
+            TOLERANCE,
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
+
+/// Returns a diff image, with mismatching pixels in red over a faded copy of the
+/// reference, and the number of mismatching pixels.
+fn compare(expected: &Framebuffer, actual: &Framebuffer) -> (Framebuffer, usize) {
+    let mut diff = Framebuffer::new(expected.width(), expected.height());
+    let mut mismatches = 0;
+    for y in 0..expected.height() {
+        for x in 0..expected.width() {
+            let (e, a) = (expected.pixel(x, y), actual.pixel(x, y));
+            let differs = e.iter().zip(a.iter()).any(|(e, a)| (i16::from(*e) - i16::from(*a)).abs() > i16::from(TOLERANCE));
+            if differs {
+                mismatches += 1;
+                diff.set_pixel(x, y, [255, 0, 0, 255]);
+            } else {
+                let luma = ((u16::from(e[0]) + u16::from(e[1]) + u16::from(e[2])) / 3) as u8;
+                diff.set_pixel(x, y, [luma / 4 + 191, luma / 4 + 191, luma / 4 + 191, 255]);
+            }
+        }
+    }
+    (diff, mismatches)
+}
+
+/// Renders `example::scene` and checks it against `example_scene.png`.
+fn check_scene(spec: &str) {
+    let selection = Selection::parse(spec).unwrap();
+    check(&spec.replace("::", "_"), &render(&mut *selection.create(), &State::new()));
+}
+
+/// Renders `example::scene` with the mouse at `mouse` and checks it against
+/// `example_scene_hover.png`.
+fn check_hover(spec: &str, mouse: (i32, i32)) {
+    let selection = Selection::parse(spec).unwrap();
+    let mut state = State::new();
+    state.mouse_pos = mouse;
+    check(&format!("{}_hover", spec.replace("::", "_")), &render(&mut *selection.create(), &state));
+}
+
+#[test]
+fn geometries_shapes() {
+    check_scene("geometries::shapes");
+}
+
+#[test]
+fn geometries_simple() {
+    check_scene("geometries::simple");
+}
+
+#[test]
+fn geometries_simple_hover() {
+    // On the ellipse's stroke, where it crosses the rectangles.
+    check_hover("geometries::simple", (250, 250));
+}
+
+#[test]
+fn geometries_path() {
+    check_scene("geometries::path");
+}
+
+#[test]
+fn geometries_arc() {
+    check_scene("geometries::arc");
+}
+
+#[test]
+fn geometries_bezier() {
+    check_scene("geometries::bezier");
+}
+
+#[test]
+fn geometries_combine() {
+    check_scene("geometries::combine");
+}
+
+#[test]
+fn geometries_group() {
+    check_scene("geometries::group");
+}
+
+#[test]
+fn brushes_solid() {
+    check_scene("brushes::solid");
+}
+
+#[test]
+fn brushes_linear() {
+    check_scene("brushes::linear");
+}
+
+#[test]
+fn brushes_radial() {
+    check_scene("brushes::radial");
+}
+
+#[test]
+fn brushes_strokes() {
+    check_scene("brushes::strokes");
+}
@@ -8,6 +8,7 @@ use winapi::shared::windef::HWND;
 use std::mem;
 use direct2d::factory::Factory;
 use direct2d::render_target::HwndRenderTarget;
+use canvas::d2d::D2DCanvas;
 use direct2d::math::SizeU;
 use helpers::verify;
 use direct2d::RenderTarget;
@@ -18,32 +19,63 @@ use winapi::shared::windef::RECT;
 use winapi::um::d2d1::D2D1_WINDOW_STATE_OCCLUDED;
 use direct2d::Error;
 use winapi::shared::winerror::D2DERR_RECREATE_TARGET;
-use examples::Example;
+use examples::{Example, Selection, EXAMPLES};
 use helpers::event::Event;
 use helpers::wnd::invalidate;
-use std::ptr;
+use helpers::wnd::set_title;
+use state::State;
 
-pub struct Gui<T: Example> {
+pub struct Gui {
     factory: Factory,
-    example: T,
-    render_target: Option<HwndRenderTarget>,
+    selection: Selection,
+    example: Box<dyn Example>,
+    canvas: Option<D2DCanvas>,
     state: State,
 }
 
-impl <T: Example>Gui<T> {
-    pub fn new() -> Gui<T> {
+impl Gui {
+    pub fn new(selection: Selection) -> Gui {
         let factory = Factory::new().unwrap();
-        let mut example = T::new();
-        example.create_device_independent_resources(&factory);
+        let mut example = selection.create();
+        example.create_device_independent_resources();
         let gui = Gui {
             factory,
+            selection,
             example,
             state: State::new(),
-            render_target: None,
+            canvas: None,
         };
         gui
     }
 
+    /// Replaces the running example; device resources are created right away if there
+    /// is a render target already.
+    fn select_example(&mut self, wnd: HWND, example: usize) {
+        let selection = Selection { example, scene: 0 };
+        let mut example = selection.create();
+        example.create_device_independent_resources();
+        if let Some(ref canvas) = self.canvas {
+            example.create_device_resources(canvas);
+        }
+        self.selection = selection;
+        self.example = example;
+        self.update_title(wnd);
+        invalidate(wnd);
+    }
+
+    fn select_scene(&mut self, wnd: HWND, scene: usize) {
+        self.selection.scene = scene;
+        self.example.set_scene(scene);
+        self.update_title(wnd);
+        invalidate(wnd);
+    }
+
+    fn update_title(&self, wnd: HWND) {
+        let info = self.selection.info();
+        let scene = self.example.scenes()[self.selection.scene];
+        set_title(wnd, &format!("{}::{} - {}", info.name, scene, info.description));
+    }
+
     fn handle(&mut self, event: Event) -> LRESULT {
         match event.message {
             WM_PAINT => self.on_paint(event),
@@ -51,12 +83,12 @@ impl <T: Example>Gui<T> {
             WM_DISPLAYCHANGE => self.on_display_change(event),
             WM_DESTROY => self.on_destroy(event),
             WM_MOUSEMOVE => self.on_mouse_move(event),
+            WM_KEYDOWN => self.on_key_down(event),
             _ => unsafe { DefWindowProcW(event.wnd, event.message, event.w_param, event.l_param) }
         }
     }
 
     fn on_paint(&mut self, event: Event) -> LRESULT {
-        self.state.paint = event;
         let mut ps;
         unsafe {
             ps = mem::uninitialized();
@@ -71,9 +103,9 @@ impl <T: Example>Gui<T> {
 
     fn on_size(&mut self, event: Event) -> LRESULT {
         let size = D2D1_SIZE_U { width: LOWORD(event.l_param as u32) as u32, height: HIWORD(event.l_param as u32) as u32 };
-        if self.render_target.is_some() {
-            if self.render_target.as_mut().unwrap().resize(SizeU(size)).is_err() {
-                self.render_target = None;
+        if self.canvas.is_some() {
+            if self.canvas.as_mut().unwrap().render_target().resize(SizeU(size)).is_err() {
+                self.canvas = None;
             }
             invalidate(event.wnd);
         }
@@ -82,7 +114,25 @@ impl <T: Example>Gui<T> {
 
     fn on_mouse_move(&mut self, event: Event) -> LRESULT {
         self.state.mouse_pos = event.mouse_pos();
-        invalidate(self.state.paint.wnd);
+        invalidate(event.wnd);
+        0
+    }
+
+    /// Tab and Shift+Tab cycle through the examples, 1 to 9 pick one directly. The left
+    /// and right arrows cycle through the scenes of the current example.
+    fn on_key_down(&mut self, event: Event) -> LRESULT {
+        let examples = EXAMPLES.len();
+        let scenes = self.example.scenes().len();
+        let Selection { example, scene } = self.selection;
+        let key = event.w_param as i32;
+        match key {
+            VK_TAB if unsafe { GetKeyState(VK_SHIFT) } < 0 => self.select_example(event.wnd, (example + examples - 1) % examples),
+            VK_TAB => self.select_example(event.wnd, (example + 1) % examples),
+            0x31..=0x39 if ((key - 0x31) as usize) < examples => self.select_example(event.wnd, (key - 0x31) as usize),
+            VK_LEFT => self.select_scene(event.wnd, (scene + scenes - 1) % scenes),
+            VK_RIGHT => self.select_scene(event.wnd, (scene + 1) % scenes),
+            _ => return unsafe { DefWindowProcW(event.wnd, event.message, event.w_param, event.l_param) },
+        }
         0
     }
 
@@ -99,7 +149,7 @@ impl <T: Example>Gui<T> {
     }
 
     fn render(&mut self, event: Event) {
-        if self.render_target.is_none() {
+        if self.canvas.is_none() {
             let mut rect = unsafe { mem::zeroed::<RECT>() };
             unsafe { GetClientRect(event.wnd, &mut rect) };
             assert_eq!(0, rect.top);
@@ -108,17 +158,17 @@ impl <T: Example>Gui<T> {
                 .with_hwnd(event.wnd)
                 .with_pixel_size(rect.right as u32, rect.bottom as u32)
                 .build().unwrap();
-            self.render_target = Some(render_target);
-            self.example.create_device_resources(self.render_target.as_ref().unwrap());
+            self.canvas = Some(D2DCanvas::new(&self.factory, render_target));
+            self.example.create_device_resources(self.canvas.as_ref().unwrap());
         }
         unsafe {
-            let state = (*(self.render_target.as_mut().unwrap().get_raw())).CheckWindowState();
+            let state = (*(self.canvas.as_mut().unwrap().render_target().get_raw())).CheckWindowState();
             if state != D2D1_WINDOW_STATE_OCCLUDED {
-                self.render_target.as_mut().unwrap().begin_draw();
-                self.example.draw(&self.state, self.render_target.as_mut().unwrap());
-                match self.render_target.as_mut().unwrap().end_draw() {
+                self.canvas.as_mut().unwrap().render_target_mut().begin_draw();
+                self.example.draw(&self.state, self.canvas.as_mut().unwrap());
+                match self.canvas.as_mut().unwrap().render_target_mut().end_draw() {
                     Err((Error::Dxgi(v), _)) if v.0 == D2DERR_RECREATE_TARGET => {
-                        self.render_target = None;
+                        self.canvas = None;
                         invalidate(event.wnd);
                     },
                     _ => {},
@@ -130,26 +180,18 @@ impl <T: Example>Gui<T> {
 
 pub unsafe extern "system" fn wnd_proc(wnd: HWND, message: UINT, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
     if message == WM_CREATE {
-        let gui = Box::new(Gui::<::examples::geometries::Geometries>::new());
+        let create = &*(l_param as *const CREATESTRUCTW);
+        let selection = *(create.lpCreateParams as *const Selection);
+        let gui = Box::new(Gui::new(selection));
+        gui.update_title(wnd);
         SetWindowLongPtrW(wnd, GWLP_USERDATA, Box::into_raw(gui) as LONG_PTR);
         0
     } else {
+        let gui = GetWindowLongPtrW(wnd, GWLP_USERDATA) as *mut Gui;
+        if gui.is_null() {
+            return DefWindowProcW(wnd, message, w_param, l_param);
+        }
         let event = Event { wnd, message, l_param, w_param };
-        let gui = &mut *(GetWindowLongPtrW(wnd, GWLP_USERDATA) as *mut Gui<::examples::geometries::Geometries>);
-        gui.handle(event)
+        (*gui).handle(event)
     }
 }
-
-pub struct State {
-    pub mouse_pos: (i32, i32),
-    pub paint: Event,
-}
-
-impl State {
-    pub fn new() -> State {
-        State {
-            mouse_pos: (0, 0),
-            paint: Event {wnd: ptr::null_mut(), l_param: 0, w_param: 0, message: 0},
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use canvas::Canvas;
+use examples::Example;
+use state::State;
+
+/// Runs one frame of the example against a canvas without a window, with the input
+/// state `state`.
+pub fn draw_frame(example: &mut dyn Example, state: &State, canvas: &mut dyn Canvas) {
+    example.create_device_independent_resources();
+    example.create_device_resources(canvas);
+    example.draw(state, canvas);
+}
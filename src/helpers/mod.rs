@@ -1,20 +1,25 @@
 use std::io;
 use helpers::wnd::create_wnd;
 use gui;
+use examples::Selection;
 
 use winapi::um::winuser::MSG;
 use helpers::msg::Msg;
 
 use winapi::um::winuser::WM_QUIT;
 use winapi::shared::minwindef::BOOL;
+use winapi::shared::minwindef::LPVOID;
 
 pub mod strings;
 pub mod wnd;
 pub mod msg;
 pub mod event;
 
-pub fn start_loop() -> io::Result<i32> {
-    let _wnd = create_wnd(Some(gui::wnd_proc));
+/// Opens the main window showing the selected example and runs the message loop until
+/// it is closed.
+pub fn start_loop(selection: Selection) -> io::Result<i32> {
+    let mut selection = selection;
+    let _wnd = create_wnd(Some(gui::wnd_proc), &mut selection as *mut Selection as LPVOID);
     loop {
         match MSG::get().unwrap() {
             MSG { message: WM_QUIT, wParam: code, .. } => {
@@ -17,10 +17,14 @@ use winapi::um::winuser::WS_VISIBLE;
 use winapi::um::winuser::WS_OVERLAPPEDWINDOW;
 use winapi::um::winuser::InvalidateRect;
 use helpers::verify_bool;
+use helpers::strings::ToWide;
+use winapi::shared::minwindef::LPVOID;
+use winapi::um::winuser::SetWindowTextW;
 
 pub type WndProcRef = unsafe extern "system" fn(wnd: HWND, message: UINT, w_param: WPARAM, l_param: LPARAM) -> LRESULT;
 
-pub fn create_wnd(wnd_proc: Option<WndProcRef>) -> HWND{
+/// `param` is handed to the window procedure in the `CREATESTRUCTW` of `WM_CREATE`.
+pub fn create_wnd(wnd_proc: Option<WndProcRef>, param: LPVOID) -> HWND{
     create_class(wnd_proc);
     unsafe {
         CreateWindowExW(
@@ -35,7 +39,7 @@ pub fn create_wnd(wnd_proc: Option<WndProcRef>) -> HWND{
             ptr::null_mut(),
             ptr::null_mut(),
             ptr::null_mut(),
-            ptr::null_mut(),
+            param,
         )
     }
 }
@@ -67,3 +71,9 @@ pub fn invalidate(wnd: HWND) {
     }
 }
 
+pub fn set_title(wnd: HWND, title: &str) {
+    let title = title.to_wide_null();
+    unsafe {
+        verify_bool(SetWindowTextW(wnd, title.as_ptr()));
+    }
+}
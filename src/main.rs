@@ -1,15 +1,81 @@
 #![allow(unused_imports)]
 #![allow(dead_code)]
+extern crate png;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+#[cfg(windows)]
 extern crate direct2d;
+#[cfg(windows)]
 extern crate winapi;
+#[cfg(windows)]
 #[macro_use]
 extern crate lazy_static;
 
+#[cfg(windows)]
 mod helpers;
+#[cfg(windows)]
 mod gui;
+mod math;
+mod enums;
+mod brush;
+mod stroke_style;
+mod geometry;
+mod canvas;
+mod state;
 mod examples;
+mod headless;
+mod render;
+#[cfg(test)]
+mod golden;
 
 fn main() {
-    helpers::start_loop().unwrap();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("render") => render::main(&args[1..]),
+        Some("list") => {
+            list();
+            Ok(())
+        }
+        _ => select_example(&args).map(run),
+    };
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn list() {
+    for example in examples::EXAMPLES {
+        println!("{:<16}{}", example.name, example.description);
+        for scene in (example.create)().scenes() {
+            println!("    {}::{}", example.name, scene);
+        }
+    }
+}
+
+/// The example named by `--example`, the first scene of the first one by default.
+fn select_example(args: &[String]) -> Result<examples::Selection, String> {
+    match args {
+        [] => Ok(examples::Selection { example: 0, scene: 0 }),
+        [flag, spec] if flag == "--example" => examples::Selection::parse(spec),
+        _ => Err(format!("usage: d2d-sandbox [--example <name>[::<scene>]] | list\n{}", render::USAGE)),
+    }
+}
+
+#[cfg(windows)]
+fn run(selection: examples::Selection) {
+    helpers::start_loop(selection).unwrap();
 }
 
+#[cfg(not(windows))]
+fn run(selection: examples::Selection) {
+    let mut example = selection.create();
+    let size = math::SizeF::new(1024.0, 768.0);
+    let mut canvas = canvas::headless::HeadlessCanvas::new(size);
+    headless::draw_frame(&mut *example, &state::State::new(), &mut canvas);
+    let scene = example.scenes()[selection.scene];
+    println!("{}::{}: {} draw calls at {}x{}", selection.info().name, scene, canvas.draw_calls(), size.width, size.height);
+    println!("{}", render::USAGE);
+}
@@ -0,0 +1,465 @@
+use enums::{ArcSize, SweepDirection};
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Point2F {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point2F {
+    #[inline]
+    pub fn new(x: f32, y: f32) -> Point2F {
+        Point2F { x, y }
+    }
+
+    #[inline]
+    pub fn origin() -> Point2F {
+        Point2F::new(0.0, 0.0)
+    }
+}
+
+impl From<(f32, f32)> for Point2F {
+    #[inline]
+    fn from((x, y): (f32, f32)) -> Point2F {
+        Point2F::new(x, y)
+    }
+}
+
+impl Add<Vector2F> for Point2F {
+    type Output = Point2F;
+
+    #[inline]
+    fn add(self, rhs: Vector2F) -> Point2F {
+        Point2F::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point2F {
+    type Output = Vector2F;
+
+    #[inline]
+    fn sub(self, rhs: Point2F) -> Vector2F {
+        Vector2F::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Sub<Vector2F> for Point2F {
+    type Output = Point2F;
+
+    #[inline]
+    fn sub(self, rhs: Vector2F) -> Point2F {
+        Point2F::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Vector2F {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vector2F {
+    #[inline]
+    pub fn new(x: f32, y: f32) -> Vector2F {
+        Vector2F { x, y }
+    }
+
+    #[inline]
+    pub fn zero() -> Vector2F {
+        Vector2F::new(0.0, 0.0)
+    }
+
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.x.hypot(self.y)
+    }
+
+    /// The vector scaled to a length of one, or the zero vector unchanged.
+    #[inline]
+    pub fn unit(&self) -> Vector2F {
+        let length = self.length();
+        if length > 0.0 {
+            *self / length
+        } else {
+            *self
+        }
+    }
+}
+
+impl From<(f32, f32)> for Vector2F {
+    #[inline]
+    fn from((x, y): (f32, f32)) -> Vector2F {
+        Vector2F::new(x, y)
+    }
+}
+
+impl Add for Vector2F {
+    type Output = Vector2F;
+
+    #[inline]
+    fn add(self, rhs: Vector2F) -> Vector2F {
+        Vector2F::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vector2F {
+    type Output = Vector2F;
+
+    #[inline]
+    fn sub(self, rhs: Vector2F) -> Vector2F {
+        Vector2F::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Vector2F {
+    type Output = Vector2F;
+
+    #[inline]
+    fn neg(self) -> Vector2F {
+        Vector2F::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<f32> for Vector2F {
+    type Output = Vector2F;
+
+    #[inline]
+    fn mul(self, rhs: f32) -> Vector2F {
+        Vector2F::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Div<f32> for Vector2F {
+    type Output = Vector2F;
+
+    #[inline]
+    fn div(self, rhs: f32) -> Vector2F {
+        Vector2F::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SizeF {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl SizeF {
+    #[inline]
+    pub fn new(width: f32, height: f32) -> SizeF {
+        SizeF { width, height }
+    }
+}
+
+impl From<(f32, f32)> for SizeF {
+    #[inline]
+    fn from((width, height): (f32, f32)) -> SizeF {
+        SizeF::new(width, height)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RectF {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl RectF {
+    #[inline]
+    pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> RectF {
+        RectF { left, top, right, bottom }
+    }
+
+    #[inline]
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.bottom - self.top
+    }
+}
+
+impl From<(f32, f32, f32, f32)> for RectF {
+    #[inline]
+    fn from((left, top, right, bottom): (f32, f32, f32, f32)) -> RectF {
+        RectF::new(left, top, right, bottom)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundedRect {
+    pub rect: RectF,
+    pub radius_x: f32,
+    pub radius_y: f32,
+}
+
+impl RoundedRect {
+    #[inline]
+    pub fn new(rect: impl Into<RectF>, radius_x: f32, radius_y: f32) -> RoundedRect {
+        RoundedRect { rect: rect.into(), radius_x, radius_y }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ellipse {
+    pub center: Point2F,
+    pub radius_x: f32,
+    pub radius_y: f32,
+}
+
+impl Ellipse {
+    #[inline]
+    pub fn new(center: impl Into<Point2F>, radius_x: f32, radius_y: f32) -> Ellipse {
+        Ellipse { center: center.into(), radius_x, radius_y }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColorF {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl ColorF {
+    #[inline]
+    pub fn uint_rgb(rgb: u32, a: f32) -> ColorF {
+        ColorF {
+            r: ((rgb >> 16) & 0xFF) as f32 / 255.0,
+            g: ((rgb >> 8) & 0xFF) as f32 / 255.0,
+            b: (rgb & 0xFF) as f32 / 255.0,
+            a,
+        }
+    }
+}
+
+impl<'a> From<&'a ColorF> for ColorF {
+    #[inline]
+    fn from(color: &'a ColorF) -> ColorF {
+        *color
+    }
+}
+
+impl From<u32> for ColorF {
+    #[inline]
+    fn from(rgb: u32) -> ColorF {
+        ColorF::uint_rgb(rgb, 1.0)
+    }
+}
+
+impl From<(u32, f32)> for ColorF {
+    #[inline]
+    fn from((rgb, a): (u32, f32)) -> ColorF {
+        ColorF::uint_rgb(rgb, a)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BezierSegment {
+    pub point1: Point2F,
+    pub point2: Point2F,
+    pub point3: Point2F,
+}
+
+impl BezierSegment {
+    #[inline]
+    pub fn new(p1: impl Into<Point2F>, p2: impl Into<Point2F>, p3: impl Into<Point2F>) -> BezierSegment {
+        BezierSegment {
+            point1: p1.into(),
+            point2: p2.into(),
+            point3: p3.into(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuadBezierSegment {
+    pub point1: Point2F,
+    pub point2: Point2F,
+}
+
+impl QuadBezierSegment {
+    #[inline]
+    pub fn new(p1: impl Into<Point2F>, p2: impl Into<Point2F>) -> QuadBezierSegment {
+        QuadBezierSegment {
+            point1: p1.into(),
+            point2: p2.into(),
+        }
+    }
+}
+
+/// One triangle of a tessellated fill.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Triangle {
+    pub point1: Point2F,
+    pub point2: Point2F,
+    pub point3: Point2F,
+}
+
+impl Triangle {
+    #[inline]
+    pub fn new(p1: impl Into<Point2F>, p2: impl Into<Point2F>, p3: impl Into<Point2F>) -> Triangle {
+        Triangle {
+            point1: p1.into(),
+            point2: p2.into(),
+            point3: p3.into(),
+        }
+    }
+}
+
+/// Elliptical arc ending at `point`. As in Direct2D, `rotation_angle` is in degrees.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ArcSegment {
+    pub point: Point2F,
+    pub size: SizeF,
+    pub rotation_angle: f32,
+    pub sweep_direction: SweepDirection,
+    pub arc_size: ArcSize,
+}
+
+impl ArcSegment {
+    #[inline]
+    pub fn new(
+        point: impl Into<Point2F>,
+        size: impl Into<SizeF>,
+        angle: f32,
+        sweep_direction: SweepDirection,
+        arc_size: ArcSize,
+    ) -> ArcSegment {
+        ArcSegment {
+            point: point.into(),
+            size: size.into(),
+            rotation_angle: angle,
+            sweep_direction,
+            arc_size,
+        }
+    }
+}
+
+/// Affine transform in the Direct2D row-vector layout: a point maps to
+/// `(x * m11 + y * m21 + dx, x * m12 + y * m22 + dy)` with
+/// `matrix = [[m11, m12], [m21, m22], [dx, dy]]`.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Matrix3x2F {
+    pub matrix: [[f32; 2]; 3],
+}
+
+impl Matrix3x2F {
+    pub const IDENTITY: Matrix3x2F = Matrix3x2F {
+        matrix: [[1.0, 0.0], [0.0, 1.0], [0.0, 0.0]],
+    };
+
+    #[inline]
+    pub fn new(matrix: [[f32; 2]; 3]) -> Matrix3x2F {
+        Matrix3x2F { matrix }
+    }
+
+    #[inline]
+    pub fn translation(v: impl Into<Vector2F>) -> Matrix3x2F {
+        let v = v.into();
+        Matrix3x2F::new([[1.0, 0.0], [0.0, 1.0], [v.x, v.y]])
+    }
+
+    #[inline]
+    pub fn scale(scale: impl Into<SizeF>, center: impl Into<Point2F>) -> Matrix3x2F {
+        let (scale, center) = (scale.into(), center.into());
+        Matrix3x2F::new([
+            [scale.width, 0.0],
+            [0.0, scale.height],
+            [center.x - scale.width * center.x, center.y - scale.height * center.y],
+        ])
+    }
+
+    /// Rotation by `angle` radians around `center`, clockwise on screen like Direct2D.
+    #[inline]
+    pub fn rotation(angle: f32, center: impl Into<Point2F>) -> Matrix3x2F {
+        let center = center.into();
+        let (sin, cos) = angle.sin_cos();
+        Matrix3x2F::new([
+            [cos, sin],
+            [-sin, cos],
+            [center.x - cos * center.x + sin * center.y, center.y - sin * center.x - cos * center.y],
+        ])
+    }
+
+    #[inline]
+    pub fn skew(angle_x: f32, angle_y: f32, center: impl Into<Point2F>) -> Matrix3x2F {
+        let center = center.into();
+        let (tan_x, tan_y) = (angle_x.tan(), angle_y.tan());
+        Matrix3x2F::new([[1.0, tan_y], [tan_x, 1.0], [-center.y * tan_x, -center.x * tan_y]])
+    }
+
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        let m = &self.matrix;
+        m[0][0] * m[1][1] - m[0][1] * m[1][0]
+    }
+
+    pub fn invert(&self) -> Option<Matrix3x2F> {
+        let det = self.determinant();
+        if det.abs() <= f32::EPSILON {
+            return None;
+        }
+        let [[a, b], [c, d], [x, y]] = self.matrix;
+        Some(Matrix3x2F::new([
+            [d / det, -b / det],
+            [-c / det, a / det],
+            [(c * y - d * x) / det, (b * x - a * y) / det],
+        ]))
+    }
+
+    #[inline]
+    pub fn is_identity(&self) -> bool {
+        *self == Matrix3x2F::IDENTITY
+    }
+}
+
+impl Default for Matrix3x2F {
+    #[inline]
+    fn default() -> Matrix3x2F {
+        Matrix3x2F::IDENTITY
+    }
+}
+
+/// `a * b` applies `a` first, then `b`.
+impl Mul for Matrix3x2F {
+    type Output = Matrix3x2F;
+
+    #[inline]
+    fn mul(self, rhs: Matrix3x2F) -> Matrix3x2F {
+        let [[a1, b1], [c1, d1], [x1, y1]] = self.matrix;
+        let [[a2, b2], [c2, d2], [x2, y2]] = rhs.matrix;
+        Matrix3x2F::new([
+            [a1 * a2 + b1 * c2, a1 * b2 + b1 * d2],
+            [c1 * a2 + d1 * c2, c1 * b2 + d1 * d2],
+            [x1 * a2 + y1 * c2 + x2, x1 * b2 + y1 * d2 + y2],
+        ])
+    }
+}
+
+impl Mul<Matrix3x2F> for Point2F {
+    type Output = Point2F;
+
+    #[inline]
+    fn mul(self, rhs: Matrix3x2F) -> Point2F {
+        let [[a, b], [c, d], [x, y]] = rhs.matrix;
+        Point2F::new(self.x * a + self.y * c + x, self.x * b + self.y * d + y)
+    }
+}
+
+impl Mul<Matrix3x2F> for Vector2F {
+    type Output = Vector2F;
+
+    #[inline]
+    fn mul(self, rhs: Matrix3x2F) -> Vector2F {
+        let [[a, b], [c, d], _] = rhs.matrix;
+        Vector2F::new(self.x * a + self.y * c, self.x * b + self.y * d)
+    }
+}
@@ -0,0 +1,136 @@
+use canvas::recording::RecordingCanvas;
+use canvas::software::SoftwareCanvas;
+use canvas::svg::SvgCanvas;
+use canvas::Canvas;
+use examples::Selection;
+use headless;
+use state::State;
+
+use math::SizeF;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "usage: d2d-sandbox render --example <name>[::<scene>] [--size <width>x<height>] [--mouse <x>,<y>] [--out <file.png|file.svg|file.json>]";
+
+/// File formats a frame can be written in, picked by the extension of the output.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    Png,
+    Svg,
+    Json,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Result<Format, String> {
+        let extension = path.extension().and_then(|extension| extension.to_str()).map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("png") => Ok(Format::Png),
+            Some("svg") => Ok(Format::Svg),
+            Some("json") => Ok(Format::Json),
+            _ => Err(format!("cannot write `{}`: the extension must be png, svg or json", path.display())),
+        }
+    }
+}
+
+/// Options of the `render` command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    pub example: String,
+    pub width: u32,
+    pub height: u32,
+    /// Mouse position the frame is drawn with, for scenes that react to hovering.
+    pub mouse: (i32, i32),
+    pub out: PathBuf,
+    pub format: Format,
+}
+
+impl Options {
+    /// Parses the arguments that follow `render`.
+    pub fn parse(args: &[String]) -> Result<Options, String> {
+        let mut example = None;
+        let mut size = (1024, 768);
+        let mut mouse = (0, 0);
+        let mut out = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("missing value for `{}`", arg));
+            match arg.as_str() {
+                "--example" => example = Some(value()?.clone()),
+                "--size" => size = parse_size(value()?)?,
+                "--mouse" => mouse = parse_point(value()?)?,
+                "--out" => out = Some(PathBuf::from(value()?)),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        let example = example.ok_or("`--example` must be specified")?;
+        let out = out.unwrap_or_else(|| PathBuf::from(format!("{}.png", example.replace("::", "_"))));
+        let format = Format::from_path(&out)?;
+        Ok(Options {
+            example,
+            width: size.0,
+            height: size.1,
+            mouse,
+            out,
+            format,
+        })
+    }
+}
+
+fn parse_size(size: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("invalid size `{}`, expected <width>x<height>", size);
+    let mut parts = size.splitn(2, 'x');
+    let width = parts.next().and_then(|w| w.parse().ok()).ok_or_else(invalid)?;
+    let height = parts.next().and_then(|h| h.parse().ok()).ok_or_else(invalid)?;
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+    Ok((width, height))
+}
+
+fn parse_point(point: &str) -> Result<(i32, i32), String> {
+    let invalid = || format!("invalid position `{}`, expected <x>,<y>", point);
+    let mut parts = point.splitn(2, ',');
+    let x = parts.next().and_then(|x| x.trim().parse().ok()).ok_or_else(invalid)?;
+    let y = parts.next().and_then(|y| y.trim().parse().ok()).ok_or_else(invalid)?;
+    Ok((x, y))
+}
+
+/// Draws one frame of the example, `name` or `name::scene`, into `canvas`.
+pub fn draw_example(spec: &str, state: &State, canvas: &mut dyn Canvas) -> Result<(), String> {
+    let selection = Selection::parse(spec)?;
+    headless::draw_frame(&mut *selection.create(), state, canvas);
+    Ok(())
+}
+
+/// Entry point of `d2d-sandbox render ...`. The output format follows the file extension,
+/// which has to be one of `png`, `svg` and `json`.
+///
+/// The example draws once into a display list, which is then replayed onto the target.
+pub fn main(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args).map_err(|e| format!("{}\n{}", e, USAGE))?;
+    let size = SizeF::new(options.width as f32, options.height as f32);
+    let mut recording = RecordingCanvas::new(size);
+    let mut state = State::new();
+    state.mouse_pos = options.mouse;
+    draw_example(&options.example, &state, &mut recording)?;
+    let frame = recording.into_display_list();
+
+    let written = match options.format {
+        Format::Svg => {
+            let mut canvas = SvgCanvas::new(size);
+            frame.replay(&mut canvas);
+            fs::write(&options.out, canvas.document())
+        }
+        Format::Json => {
+            let json = frame.to_json()?;
+            fs::write(&options.out, json)
+        }
+        Format::Png => {
+            let mut canvas = SoftwareCanvas::new(options.width, options.height);
+            frame.replay(&mut canvas);
+            canvas.framebuffer().save_png(&options.out)
+        }
+    };
+    written.map_err(|e| format!("cannot write `{}`: {}", options.out.display(), e))
+}
@@ -0,0 +1,18 @@
+use math::Point2F;
+
+pub struct State {
+    pub mouse_pos: (i32, i32),
+}
+
+impl State {
+    pub fn new() -> State {
+        State {
+            mouse_pos: (0, 0),
+        }
+    }
+
+    /// The mouse position in canvas coordinates.
+    pub fn mouse_point(&self) -> Point2F {
+        Point2F::new(self.mouse_pos.0 as f32, self.mouse_pos.1 as f32)
+    }
+}
@@ -0,0 +1,138 @@
+use enums::{CapStyle, DashStyle, LineJoin};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StrokeStyle {
+    start_cap: CapStyle,
+    end_cap: CapStyle,
+    dash_cap: CapStyle,
+    line_join: LineJoin,
+    miter_limit: f32,
+    dash_style: DashStyle,
+    dash_offset: f32,
+    dashes: Vec<f32>,
+}
+
+impl StrokeStyle {
+    pub fn create() -> StrokeStyleBuilder {
+        StrokeStyleBuilder::new()
+    }
+
+    pub fn get_start_cap(&self) -> CapStyle {
+        self.start_cap
+    }
+
+    pub fn get_end_cap(&self) -> CapStyle {
+        self.end_cap
+    }
+
+    pub fn get_dash_cap(&self) -> CapStyle {
+        self.dash_cap
+    }
+
+    pub fn get_line_join(&self) -> LineJoin {
+        self.line_join
+    }
+
+    pub fn get_miter_limit(&self) -> f32 {
+        self.miter_limit
+    }
+
+    pub fn get_dash_style(&self) -> DashStyle {
+        self.dash_style
+    }
+
+    pub fn get_dash_offset(&self) -> f32 {
+        self.dash_offset
+    }
+
+    pub fn get_dashes(&self) -> &[f32] {
+        &self.dashes
+    }
+
+    /// Alternating dash and gap lengths, in multiples of the stroke width, for the
+    /// dash style; empty for solid strokes. The predefined patterns are Direct2D's.
+    pub fn dash_pattern(&self) -> &[f32] {
+        match self.dash_style {
+            DashStyle::Solid => &[],
+            DashStyle::Dash => &[2.0, 2.0],
+            DashStyle::Dot => &[0.0, 2.0],
+            DashStyle::DashDot => &[2.0, 2.0, 0.0, 2.0],
+            DashStyle::DashDotDot => &[2.0, 2.0, 0.0, 2.0, 0.0, 2.0],
+            DashStyle::Custom => &self.dashes,
+        }
+    }
+}
+
+impl Default for StrokeStyle {
+    fn default() -> StrokeStyle {
+        StrokeStyle::create().build()
+    }
+}
+
+pub struct StrokeStyleBuilder {
+    style: StrokeStyle,
+}
+
+impl StrokeStyleBuilder {
+    pub fn new() -> Self {
+        // default values taken from D2D1::StrokeStyleProperties in d2d1helper.h
+        StrokeStyleBuilder {
+            style: StrokeStyle {
+                start_cap: CapStyle::Flat,
+                end_cap: CapStyle::Flat,
+                dash_cap: CapStyle::Flat,
+                line_join: LineJoin::Miter,
+                miter_limit: 10.0,
+                dash_style: DashStyle::Solid,
+                dash_offset: 0.0,
+                dashes: Vec::new(),
+            },
+        }
+    }
+
+    pub fn build(self) -> StrokeStyle {
+        self.style
+    }
+
+    pub fn with_start_cap(mut self, start_cap: CapStyle) -> Self {
+        self.style.start_cap = start_cap;
+        self
+    }
+
+    pub fn with_end_cap(mut self, end_cap: CapStyle) -> Self {
+        self.style.end_cap = end_cap;
+        self
+    }
+
+    pub fn with_dash_cap(mut self, dash_cap: CapStyle) -> Self {
+        self.style.dash_cap = dash_cap;
+        self
+    }
+
+    pub fn with_line_join(mut self, line_join: LineJoin) -> Self {
+        self.style.line_join = line_join;
+        self
+    }
+
+    pub fn with_miter_limit(mut self, miter_limit: f32) -> Self {
+        self.style.miter_limit = miter_limit;
+        self
+    }
+
+    pub fn with_dash_style(mut self, dash_style: DashStyle) -> Self {
+        self.style.dash_style = dash_style;
+        self
+    }
+
+    pub fn with_dash_offset(mut self, dash_offset: f32) -> Self {
+        self.style.dash_offset = dash_offset;
+        self
+    }
+
+    /// Custom dash pattern, in multiples of the stroke width. Implies `DashStyle::Custom`.
+    pub fn with_dashes(mut self, dashes: &[f32]) -> Self {
+        self.style.dash_style = DashStyle::Custom;
+        self.style.dashes = dashes.to_vec();
+        self
+    }
+}